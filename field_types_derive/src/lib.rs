@@ -0,0 +1,2653 @@
+/*!
+Derive macros for the [`field_types`](https://docs.rs/field_types) crate.
+
+Use them through the re-exports of `field_types` crate, because the generated code
+refers to the traits of that crate.
+!*/
+
+extern crate proc_macro;
+extern crate proc_macro2;
+extern crate syn;
+extern crate quote;
+extern crate heck;
+
+use std::collections::HashSet;
+use proc_macro::TokenStream;
+use proc_macro2::{Delimiter, Span, TokenStream as TokenStream2, TokenTree};
+use syn::{
+    DeriveInput, Ident, Type, Attribute, Fields, GenericParam, Generics, Lifetime, Lit, Meta, MetaList, MetaNameValue,
+    NestedMeta, Path, PathArguments, PathSegment, Visibility, WherePredicate,
+    punctuated::Punctuated,
+    visit::{self, Visit},
+};
+use quote::{quote, ToTokens};
+use heck::{CamelCase, SnakeCase};
+
+#[proc_macro_derive(FieldType, attributes(field_types, field_type, field_types_derive, field_type_derive))]
+pub fn field_type(input: TokenStream) -> TokenStream {
+    let ast: DeriveInput = syn::parse(input).unwrap();
+    let (ty, generics) = (&ast.ident, &ast.generics);
+    let options = get_options(&ast.attrs, &["field_types", "field_type"], FIELD_TYPE_OPTIONS);
+    let vis = options.vis.as_ref().unwrap_or(&ast.vis);
+    let enum_ty = options.name.clone()
+        .unwrap_or_else(|| Ident::new(&(ty.to_string() + "FieldType"), Span::call_site()));
+    let array_method = options.array_method.clone()
+        .unwrap_or_else(|| Ident::new("into_field_type_array", Span::call_site()));
+    let derive = get_enum_derive(&ast.attrs, &["field_types_derive", "field_type_derive"], quote! {});
+
+    let mut fields = filter_fields(match ast.data {
+        syn::Data::Struct(ref s) => &s.fields,
+        _ => panic!("FieldType can only be derived for structures"),
+    }, "field_type");
+
+    if options.visible_only {
+        fields.retain(|field| is_visible(&field.vis, vis));
+    }
+
+    if fields.is_empty() {
+        panic!("FieldType can only be derived for non-empty structures");
+    }
+
+    let has_flatten = check_flatten_fields(generics, &fields);
+    if has_flatten && options.diff {
+        panic!("Option `diff` can't be used with `flatten` fields.");
+    }
+    if has_flatten && options.tracked.is_some() {
+        panic!("Option `tracked` can't be used with `flatten` fields.");
+    }
+
+    let field_type_variants = fields.iter()
+        .map(|FieldInfo { ty: field_ty, variant: variant_ident, docs, flatten, .. }| {
+            if *flatten {
+                quote! {
+                    #(#docs)*
+                    #variant_ident(<#field_ty as ::field_types::FieldTypes>::Value)
+                }
+            } else {
+                quote! {
+                    #(#docs)*
+                    #variant_ident(#field_ty)
+                }
+            }
+        });
+
+    let field_type_array = if has_flatten {
+        let field_type_iters = fields.iter()
+            .map(|FieldInfo { ident: field_ident, variant: variant_ident, flatten, .. }| {
+                if *flatten {
+                    quote! {
+                        ::core::iter::Iterator::map(::field_types::FieldTypes::into_field_types(#field_ident), #enum_ty::#variant_ident)
+                    }
+                } else {
+                    quote! {
+                        ::core::iter::once(#enum_ty::#variant_ident(#field_ident))
+                    }
+                }
+            });
+        let field_type_iter = field_type_iters
+            .reduce(|iter, next| quote! { ::core::iter::Iterator::chain(#iter, #next) })
+            .expect("FieldType can only be derived for non-empty structures");
+        quote! {
+            let mut fields = #field_type_iter;
+            ::core::array::from_fn(|_| {
+                ::core::iter::Iterator::next(&mut fields)
+                    .expect("FieldTypes must have a value for every variant")
+            })
+        }
+    } else {
+        let field_type_constructs = fields.iter()
+            .map(|FieldInfo { ident: field_ident, variant: variant_ident, .. }| {
+                quote! {
+                    #enum_ty::#variant_ident(#field_ident)
+                }
+            });
+        quote! {
+            [#(#field_type_constructs),*]
+        }
+    };
+
+    let fields_idents = fields.iter()
+        .map(|FieldInfo { ident: field_ident, .. }| {
+            quote! {
+                #field_ident
+            }
+        });
+
+    let destructuring = quote! { #ty { #(#fields_idents,)* .. } };
+
+    let fields_count = get_field_offsets(fields.iter()
+        .map(|FieldInfo { ty: field_ty, flatten, .. }| if *flatten {
+            Some(quote! { <#field_ty as ::field_types::FieldTypes>::COUNT })
+        } else {
+            None
+        }))
+        .pop()
+        .expect("FieldType can only be derived for non-empty structures");
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let enum_generics = filter_generics(generics, &fields);
+    let (_, enum_ty_generics, enum_where_clause) = enum_generics.split_for_impl();
+
+    let field_type_names_table = get_names_table(&fields, &get_field_type_child_names(&fields), &fields_count);
+
+    // The table is evaluated at compile time, so the duplicate names of the flattened fields are rejected
+    let field_type_names_check = if has_flatten {
+        quote! {
+            const _: &[&str] = #field_type_names_table;
+        }
+    } else {
+        quote! {}
+    };
+
+    // The enum can't be an associated type of the struct, if it is less visible than the struct
+    let field_types_impl = if is_visible(vis, &ast.vis) {
+        quote! {
+            impl #impl_generics ::field_types::FieldTypes for #ty #ty_generics
+                #where_clause
+            {
+                type Value = #enum_ty #enum_ty_generics;
+                type IntoFieldTypes = ::core::array::IntoIter<#enum_ty #enum_ty_generics, { #fields_count }>;
+
+                const COUNT: usize = #fields_count;
+
+                const NAMES: &'static [&'static str] = #field_type_names_table;
+
+                fn into_field_types(self) -> Self::IntoFieldTypes {
+                    let fields: [#enum_ty #enum_ty_generics; #fields_count] = ::core::convert::From::from(self);
+                    ::core::iter::IntoIterator::into_iter(fields)
+                }
+            }
+
+            impl #impl_generics ::core::iter::IntoIterator for #ty #ty_generics
+                #where_clause
+            {
+                type Item = #enum_ty #enum_ty_generics;
+                type IntoIter = ::core::array::IntoIter<#enum_ty #enum_ty_generics, { #fields_count }>;
+
+                fn into_iter(self) -> Self::IntoIter {
+                    let fields: [#enum_ty #enum_ty_generics; #fields_count] = ::core::convert::From::from(self);
+                    ::core::iter::IntoIterator::into_iter(fields)
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let field_type_diff = if options.diff {
+        let field_diffs = fields.iter()
+            .map(|FieldInfo { ident: field_ident, variant: variant_ident, .. }| {
+                quote! {
+                    if self.#field_ident != other.#field_ident {
+                        ::core::option::Option::Some(#enum_ty::#variant_ident(::core::clone::Clone::clone(&other.#field_ident)))
+                    } else {
+                        ::core::option::Option::None
+                    }
+                }
+            });
+        let field_bounds = fields.iter()
+            .map(|FieldInfo { ty: field_ty, .. }| quote! { #field_ty: ::core::cmp::PartialEq + ::core::clone::Clone });
+        quote! {
+            impl #impl_generics #ty #ty_generics
+                #where_clause
+            {
+                #vis fn diff(&self, other: &Self) -> impl ::core::iter::Iterator<Item = #enum_ty #enum_ty_generics>
+                    where #(#field_bounds),*
+                {
+                    let fields: [::core::option::Option<#enum_ty #enum_ty_generics>; #fields_count] = [#(#field_diffs),*];
+                    ::core::iter::Iterator::flatten(::core::iter::IntoIterator::into_iter(fields))
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let field_sets = fields.iter()
+        .map(|FieldInfo { ident: field_ident, variant: variant_ident, flatten, .. }| {
+            if *flatten {
+                quote! {
+                    #enum_ty::#variant_ident(value) => #enum_ty::#variant_ident(
+                        ::field_types::SetField::set_field(&mut self.#field_ident, value)
+                    )
+                }
+            } else {
+                quote! {
+                    #enum_ty::#variant_ident(value) => #enum_ty::#variant_ident(
+                        ::core::mem::replace(&mut self.#field_ident, value)
+                    )
+                }
+            }
+        });
+
+    let field_type_history = if options.history {
+        if !cfg!(feature = "alloc") {
+            panic!("Option `history` requires `alloc` feature of `field_types` crate.");
+        }
+        if !is_visible(vis, &ast.vis) {
+            panic!("Option `history` requires the `..FieldType` enum to be as visible as the struct.");
+        }
+        if fields.iter().any(|FieldInfo { vis: field_vis, .. }| !is_visible(field_vis, &ast.vis)) {
+            panic!("Option `history` requires the fields to be as visible as the struct, \
+                other fields can be skipped with `visible_only` option.");
+        }
+        let history_ty = Ident::new(&(ty.to_string() + "History"), Span::call_site());
+        let params = generics.params.iter()
+            .map(|param| match param {
+                GenericParam::Type(param) => param.ident.to_token_stream(),
+                GenericParam::Lifetime(param) => param.lifetime.to_token_stream(),
+                GenericParam::Const(param) => {
+                    let (const_ident, const_ty) = (&param.ident, &param.ty);
+                    quote! { const #const_ident: #const_ty }
+                },
+            });
+        quote! {
+            impl #impl_generics ::field_types::SetField for #ty #ty_generics
+                #where_clause
+            {
+                fn set_field(&mut self, value: Self::Value) -> Self::Value {
+                    match value {
+                        #(#field_sets),*
+                    }
+                }
+            }
+
+            #vis type #history_ty<#(#params),*> = ::field_types::History<#ty #ty_generics>;
+        }
+    } else {
+        quote! {}
+    };
+
+    let field_type_tracked = if options.tracked.is_some() {
+        let tracked_ty = Ident::new(&(ty.to_string() + "Tracked"), Span::call_site());
+        let field_values = fields.iter().enumerate()
+            .map(|(index, FieldInfo { ident: field_ident, variant: variant_ident, .. })| {
+                let field_name = field_ident.to_string();
+                quote! {
+                    #field_name => fields[#index] = ::core::option::Option::Some(
+                        #enum_ty::#variant_ident(::core::clone::Clone::clone(&value.#field_ident))
+                    )
+                }
+            });
+        let no_fields = fields.iter().map(|_| quote! { ::core::option::Option::None });
+        let field_bounds = fields.iter()
+            .map(|FieldInfo { ty: field_ty, .. }| quote! { #field_ty: ::core::clone::Clone });
+        quote! {
+            impl #impl_generics #tracked_ty #ty_generics
+                #where_clause
+            {
+                #vis fn take_changed_values(&mut self) -> impl ::core::iter::Iterator<Item = #enum_ty #enum_ty_generics>
+                    where #(#field_bounds),*
+                {
+                    let changes = self.take_changes();
+                    let value = self.get();
+                    let mut fields: [::core::option::Option<#enum_ty #enum_ty_generics>; #fields_count] = [#(#no_fields),*];
+                    for name in changes.iter() {
+                        match ::field_types::FieldName::name(&name) {
+                            #(#field_values,)*
+                            _ => {}
+                        }
+                    }
+                    ::core::iter::Iterator::flatten(::core::iter::IntoIterator::into_iter(fields))
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let field_type_serde = if options.serde {
+        get_field_type_serde_impl(&enum_ty, &enum_generics, &fields)
+    } else {
+        quote! {}
+    };
+
+    let tokens = quote! {
+        #derive
+        #vis enum #enum_ty #enum_generics
+            #enum_where_clause
+        {
+            #(#field_type_variants),*
+        }
+
+        impl #impl_generics ::core::convert::From<#ty #ty_generics> for [#enum_ty #enum_ty_generics; #fields_count]
+            #where_clause
+        {
+            fn from(source: #ty #ty_generics) -> Self {
+                let #destructuring = source;
+                #field_type_array
+            }
+        }
+
+        impl #impl_generics #ty #ty_generics
+            #where_clause
+        {
+            #vis fn #array_method(self) -> [#enum_ty #enum_ty_generics; #fields_count] {
+                let #destructuring = self;
+                #field_type_array
+            }
+
+            #vis fn into_field_types(self) -> impl ::core::iter::ExactSizeIterator<Item = #enum_ty #enum_ty_generics>
+                + ::core::iter::DoubleEndedIterator
+            {
+                ::core::iter::IntoIterator::into_iter(self.#array_method())
+            }
+        }
+
+        #field_types_impl
+
+        #field_type_names_check
+
+        #field_type_diff
+
+        #field_type_tracked
+
+        #field_type_history
+
+        #field_type_serde
+    };
+    tokens.into()
+}
+
+#[proc_macro_derive(FieldName, attributes(field_types, field_name, field_types_derive, field_name_derive))]
+pub fn field_name(input: TokenStream) -> TokenStream {
+    let ast: DeriveInput = syn::parse(input).unwrap();
+    let (ty, generics) = (&ast.ident, &ast.generics);
+    let options = get_options(&ast.attrs, &["field_types", "field_name"], FIELD_NAME_OPTIONS);
+    let vis = options.vis.as_ref().unwrap_or(&ast.vis);
+    let enum_ty = options.name.clone()
+        .unwrap_or_else(|| Ident::new(&(ty.to_string() + "FieldName"), Span::call_site()));
+    let array_method = options.array_method.clone()
+        .unwrap_or_else(|| Ident::new("as_field_name_array", Span::call_site()));
+    let derive = get_enum_derive(&ast.attrs, &["field_types_derive", "field_name_derive"],
+                            quote! {
+                                #[derive(::core::fmt::Debug, ::core::cmp::PartialEq, ::core::cmp::Eq,
+                                         ::core::clone::Clone, ::core::marker::Copy)]
+                            });
+
+    let struct_fields = match ast.data {
+        syn::Data::Struct(ref s) => &s.fields,
+        _ => panic!("FieldName can only be derived for structures"),
+    };
+    let mut fields = filter_fields(struct_fields, "field_name");
+
+    if options.visible_only {
+        fields.retain(|field| is_visible(&field.vis, vis));
+    }
+
+    if fields.is_empty() {
+        panic!("FieldName can only be derived for non-empty structures");
+    }
+
+    let has_flatten = check_flatten_fields(generics, &fields);
+    if has_flatten {
+        let unsupported = [
+            ("index", options.index),
+            ("map", options.map),
+            ("visit", options.visit.is_some()),
+            ("any", options.any),
+            ("field_keys", options.field_keys),
+            ("partial", options.partial.is_some()),
+            ("diff", options.diff),
+            ("json_patch", options.json_patch),
+            ("tracked", options.tracked.is_some()),
+        ];
+        if let Some((option, _)) = unsupported.iter().find(|(_, enabled)| *enabled) {
+            panic!("Option `{}` can't be used with `flatten` fields.", option);
+        }
+        if fields.iter().any(|field| field.nested) {
+            panic!("`nested` and `flatten` fields can't be used in the same structure.");
+        }
+    }
+
+    let child_names = fields.iter()
+        .map(|FieldInfo { ty: field_ty, flatten, .. }| if *flatten {
+            Some(quote! { <#field_ty as ::field_types::FieldNames>::Name })
+        } else {
+            None
+        })
+        .collect::<Vec<_>>();
+    let field_name_offsets = get_field_offsets(child_names.iter()
+        .map(|child_name| child_name.as_ref().map(|child_name| quote! { <#child_name as ::field_types::FieldName>::COUNT })));
+
+    let field_names_table = get_names_table(&fields, &child_names.iter()
+        .map(|child_name| child_name.as_ref().map(|child_name| quote! { <#child_name as ::field_types::FieldName>::NAMES }))
+        .collect::<Vec<_>>(), field_name_offsets.last().expect("FieldName can only be derived for non-empty structures"));
+
+    // The table is evaluated at compile time, so the duplicate names of the flattened fields are rejected
+    let field_names_check = if has_flatten {
+        quote! {
+            const _: &[&str] = <#enum_ty as ::field_types::FieldName>::NAMES;
+        }
+    } else {
+        quote! {}
+    };
+
+    let field_name_variants = fields.iter()
+        .zip(&child_names)
+        .map(|(FieldInfo { variant: variant_ident, docs, .. }, child_name)| {
+            match child_name {
+                Some(child_name) => quote! {
+                    #(#docs)*
+                    #variant_ident(#child_name)
+                },
+                None => quote! {
+                    #(#docs)*
+                    #variant_ident
+                },
+            }
+        });
+
+    let field_name_to_strs = fields.iter()
+        .zip(&field_name_offsets)
+        .map(|(FieldInfo { ident: field_ident, variant: variant_ident, flatten, prefix, .. }, offset)| {
+            if prefix.is_some() {
+                return quote! {
+                    #enum_ty::#variant_ident(ref name) =>
+                        <#enum_ty as ::field_types::FieldName>::NAMES[#offset + ::field_types::FieldName::index(name)]
+                };
+            }
+            if *flatten {
+                return quote! {
+                    #enum_ty::#variant_ident(ref name) => ::field_types::FieldName::name(name)
+                };
+            }
+            let field_name = field_ident.to_string();
+            quote! {
+                #enum_ty::#variant_ident => #field_name
+            }
+        });
+
+    let field_name_descriptions = fields.iter()
+        .map(|FieldInfo { variant: variant_ident, docs, flatten, .. }| {
+            if *flatten {
+                return quote! {
+                    #enum_ty::#variant_ident(ref name) => ::field_types::FieldName::description(name)
+                };
+            }
+            let description = match get_doc_text(docs) {
+                Some(text) => quote! { ::core::option::Option::Some(#text) },
+                None => quote! { ::core::option::Option::None },
+            };
+            quote! {
+                #enum_ty::#variant_ident => #description
+            }
+        });
+
+    let field_name_type_names = fields.iter()
+        .map(|FieldInfo { ty: field_ty, variant: variant_ident, flatten, .. }| {
+            if *flatten {
+                return quote! {
+                    #enum_ty::#variant_ident(ref name) => ::field_types::FieldName::type_name(name)
+                };
+            }
+            let type_name = type_to_string(field_ty);
+            quote! {
+                #enum_ty::#variant_ident => #type_name
+            }
+        });
+
+    let generic_names = generics.params.iter()
+        .map(generic_param_name)
+        .chain(Some("Self".to_string()))
+        .collect::<HashSet<_>>();
+    let field_name_type_ids = fields.iter()
+        .map(|FieldInfo { ty: field_ty, variant: variant_ident, flatten, .. }| {
+            if *flatten {
+                return quote! {
+                    #enum_ty::#variant_ident(ref name) => ::field_types::FieldName::type_id(name)
+                };
+            }
+            let mut names = GenericNames::default();
+            names.visit_type(field_ty);
+            let type_id = if names.0.is_disjoint(&generic_names) {
+                quote! { ::core::option::Option::Some(::core::any::TypeId::of::<#field_ty>()) }
+            } else {
+                quote! { ::core::option::Option::None }
+            };
+            quote! {
+                #enum_ty::#variant_ident => #type_id
+            }
+        });
+
+    let field_name_by_strs = fields.iter()
+        .zip(&child_names)
+        .map(|(FieldInfo { variant: variant_ident, prefix, .. }, child_name)| {
+            match child_name {
+                Some(child_name) if prefix.is_some() => quote! {
+                    if let ::core::option::Option::Some(name) = ::core::primitive::str::strip_prefix(name, #prefix) {
+                        if let ::core::option::Option::Some(name) = <#child_name as ::field_types::FieldName>::by_name(name) {
+                            return ::core::option::Option::Some(#enum_ty::#variant_ident(name))
+                        }
+                    }
+                },
+                Some(child_name) => quote! {
+                    if let ::core::option::Option::Some(name) = <#child_name as ::field_types::FieldName>::by_name(name) {
+                        return ::core::option::Option::Some(#enum_ty::#variant_ident(name))
+                    }
+                },
+                None => quote! {
+                    if #enum_ty::#variant_ident.name() == name { return ::core::option::Option::Some(#enum_ty::#variant_ident) }
+                },
+            }
+        });
+
+    let field_name_to_indices = fields.iter()
+        .zip(&field_name_offsets)
+        .map(|(FieldInfo { variant: variant_ident, flatten, .. }, offset)| {
+            if *flatten {
+                quote! {
+                    #enum_ty::#variant_ident(ref name) => #offset + ::field_types::FieldName::index(name)
+                }
+            } else {
+                quote! {
+                    #enum_ty::#variant_ident => #offset
+                }
+            }
+        });
+
+    let field_name_from_index = if has_flatten {
+        let from_indices = fields.iter()
+            .zip(&field_name_offsets)
+            .zip(&child_names)
+            .map(|((FieldInfo { variant: variant_ident, .. }, offset), child_name)| {
+                match child_name {
+                    Some(child_name) => quote! {
+                        if (#offset..#offset + <#child_name as ::field_types::FieldName>::COUNT).contains(&index) {
+                            return <#child_name as ::field_types::FieldName>::from_index(index - #offset)
+                                .map(#enum_ty::#variant_ident);
+                        }
+                    },
+                    None => quote! {
+                        if index == #offset { return ::core::option::Option::Some(#enum_ty::#variant_ident) }
+                    },
+                }
+            });
+        quote! {
+            #(#from_indices)*
+            ::core::option::Option::None
+        }
+    } else {
+        let from_indices = fields.iter()
+            .enumerate()
+            .map(|(index, FieldInfo { variant: variant_ident, .. })| {
+                quote! {
+                    #index => ::core::option::Option::Some(#enum_ty::#variant_ident)
+                }
+            });
+        quote! {
+            match index {
+                #(#from_indices,)*
+                _ => ::core::option::Option::None,
+            }
+        }
+    };
+
+    let field_name_array = if has_flatten {
+        quote! {
+            ::core::array::from_fn(|index| {
+                <#enum_ty as ::field_types::FieldName>::from_index(index)
+                    .expect("FieldName index must correspond to a variant")
+            })
+        }
+    } else {
+        let field_name_constructs = fields.iter()
+            .map(|FieldInfo { variant: variant_ident, .. }| {
+                quote! {
+                    #enum_ty::#variant_ident
+                }
+            });
+        quote! {
+            [#(#field_name_constructs),*]
+        }
+    };
+
+    let fields_count = field_name_offsets.last()
+        .expect("FieldName can only be derived for non-empty structures");
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let field_name_set = if has_flatten || fields.len() <= 128 {
+        let set_ty = Ident::new(&(enum_ty.to_string() + "Set"), Span::call_site());
+        quote! {
+            #vis type #set_ty = ::field_types::FieldNameSet<#enum_ty>;
+        }
+    } else {
+        quote! {}
+    };
+
+    let map_ty = Ident::new(&(enum_ty.to_string() + "Map"), Span::call_site());
+    let field_name_index = if options.index {
+        get_index_impl(ty, generics, vis, &enum_ty, struct_fields, &fields)
+    } else {
+        quote! {}
+    };
+
+    let field_name_map_fns = if options.map {
+        get_map_impl(ty, generics, vis, &enum_ty, struct_fields, &fields)
+    } else {
+        quote! {}
+    };
+
+    let field_name_visit = match options.visit {
+        Some(ref traits) => get_visit_impl(ty, generics, vis, &enum_ty, traits, &fields),
+        None => quote! {},
+    };
+
+    let field_name_any = if options.any {
+        get_any_impl(ty, generics, vis, &enum_ty, &fields)
+    } else {
+        quote! {}
+    };
+
+    let field_name_keys = if options.field_keys {
+        get_field_keys_impl(ty, generics, vis, &fields)
+    } else {
+        quote! {}
+    };
+
+    let field_name_diff = if options.diff {
+        check_field_name_set_limit("diff", &fields);
+        let fields_idents = fields.iter()
+            .map(|FieldInfo { ident: field_ident, .. }| field_ident);
+        let field_variants = fields.iter()
+            .map(|FieldInfo { variant: variant_ident, .. }| variant_ident);
+        let field_bounds = fields.iter()
+            .map(|FieldInfo { ty: field_ty, .. }| quote! { #field_ty: ::core::cmp::PartialEq });
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+        quote! {
+            impl #impl_generics #ty #ty_generics
+                #where_clause
+            {
+                #vis fn changed_fields(&self, other: &Self) -> ::field_types::FieldNameSet<#enum_ty>
+                    where #(#field_bounds),*
+                {
+                    let mut changed = ::field_types::FieldNameSet::empty();
+                    #(
+                        if self.#fields_idents != other.#fields_idents {
+                            changed.insert(#enum_ty::#field_variants);
+                        }
+                    )*
+                    changed
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let field_name_tracked = match options.tracked {
+        Some(ref derives) => get_tracked_impl(ty, generics, vis, &enum_ty, derives, &fields),
+        None => quote! {},
+    };
+
+    let field_name_json = if options.json_patch {
+        get_json_patch_impl(ty, generics, vis, &enum_ty, &fields)
+    } else {
+        quote! {}
+    };
+
+    let field_name_partial = match options.partial {
+        Some(ref derives) => get_partial_impl(ty, generics, vis, &enum_ty, derives, struct_fields, &fields),
+        None => quote! {},
+    };
+
+    let field_name_path = get_path_impl(ty, generics, vis, &ast.vis, &enum_ty, &fields);
+
+    let field_name_serde = if options.serde {
+        if !cfg!(feature = "serde") {
+            panic!("Option `serde` requires `serde` feature of `field_types` crate.");
+        }
+        quote! {
+            impl ::field_types::__private::serde::Serialize for #enum_ty {
+                fn serialize<S>(&self, serializer: S) -> ::core::result::Result<S::Ok, S::Error>
+                    where S: ::field_types::__private::serde::Serializer
+                {
+                    ::field_types::__private::serialize_field_name(self, serializer)
+                }
+            }
+
+            impl<'de> ::field_types::__private::serde::Deserialize<'de> for #enum_ty {
+                fn deserialize<D>(deserializer: D) -> ::core::result::Result<Self, D::Error>
+                    where D: ::field_types::__private::serde::Deserializer<'de>
+                {
+                    ::field_types::__private::deserialize_field_name(deserializer)
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let field_name_map = quote! {
+        #vis type #map_ty<V> = ::field_types::FieldNameMap<#enum_ty, V, { #fields_count }>;
+    };
+
+    // The enum can't be an associated type of the struct, if it is less visible than the struct
+    let field_names_impl = if is_visible(vis, &ast.vis) {
+        quote! {
+            impl #impl_generics ::field_types::FieldNames for #ty #ty_generics
+                #where_clause
+            {
+                type Name = #enum_ty;
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let tokens = quote! {
+        #derive
+        #vis enum #enum_ty {
+            #(#field_name_variants),*
+        }
+
+        impl #enum_ty {
+            #vis fn name(&self) -> &'static str {
+                match *self {
+                    #(#field_name_to_strs),*
+                }
+            }
+
+            #vis fn by_name(name: &str) -> ::core::option::Option<Self> {
+                #(#field_name_by_strs)*
+                ::core::option::Option::None
+            }
+
+            #vis fn description(&self) -> ::core::option::Option<&'static str> {
+                match *self {
+                    #(#field_name_descriptions),*
+                }
+            }
+
+            #vis fn type_name(&self) -> &'static str {
+                match *self {
+                    #(#field_name_type_names),*
+                }
+            }
+
+            #vis fn type_id(&self) -> ::core::option::Option<::core::any::TypeId> {
+                match *self {
+                    #(#field_name_type_ids),*
+                }
+            }
+        }
+
+        impl #impl_generics #ty #ty_generics
+            #where_clause
+        {
+            #vis fn #array_method() -> [#enum_ty; #fields_count] {
+                #field_name_array
+            }
+
+            #vis fn field_names() -> impl ::core::iter::ExactSizeIterator<Item = #enum_ty>
+                + ::core::iter::DoubleEndedIterator
+            {
+                ::core::iter::IntoIterator::into_iter(Self::#array_method())
+            }
+        }
+
+        impl #impl_generics ::core::convert::From<&#ty #ty_generics> for [#enum_ty; #fields_count]
+            #where_clause
+        {
+            fn from(_source: &#ty #ty_generics) -> Self {
+                #field_name_array
+            }
+        }
+
+        impl ::field_types::FieldName for #enum_ty {
+            const COUNT: usize = #fields_count;
+
+            const NAMES: &'static [&'static str] = #field_names_table;
+
+            fn name(&self) -> &'static str {
+                #enum_ty::name(self)
+            }
+
+            fn by_name(name: &str) -> ::core::option::Option<Self> {
+                #enum_ty::by_name(name)
+            }
+
+            fn index(&self) -> usize {
+                match *self {
+                    #(#field_name_to_indices),*
+                }
+            }
+
+            fn from_index(index: usize) -> ::core::option::Option<Self> {
+                #field_name_from_index
+            }
+
+            fn description(&self) -> ::core::option::Option<&'static str> {
+                #enum_ty::description(self)
+            }
+
+            fn type_name(&self) -> &'static str {
+                #enum_ty::type_name(self)
+            }
+
+            fn type_id(&self) -> ::core::option::Option<::core::any::TypeId> {
+                #enum_ty::type_id(self)
+            }
+        }
+
+        #field_name_set
+
+        #field_name_map
+
+        #field_name_index
+
+        #field_name_map_fns
+
+        #field_name_visit
+
+        #field_name_any
+
+        #field_name_keys
+
+        #field_name_path
+
+        #field_name_partial
+
+        #field_name_diff
+
+        #field_name_json
+
+        #field_name_tracked
+
+        #field_name_serde
+
+        #field_names_impl
+
+        #field_names_check
+    };
+    tokens.into()
+}
+
+/// Generates serde implementations for the `..FieldType` enum as single-entry maps `{ "field": value }`.
+fn get_field_type_serde_impl(enum_ty: &Ident, enum_generics: &Generics, fields: &[FieldInfo]) -> TokenStream2 {
+    if !cfg!(feature = "serde") {
+        panic!("Option `serde` requires `serde` feature of `field_types` crate.");
+    }
+
+    let (impl_generics, ty_generics, where_clause) = enum_generics.split_for_impl();
+    let mut de_generics = enum_generics.clone();
+    de_generics.params.insert(0, syn::parse_quote! { 'de });
+    let (de_impl_generics, _, _) = de_generics.split_for_impl();
+
+    let child_values = fields.iter()
+        .map(|FieldInfo { ty: field_ty, flatten, .. }| if *flatten {
+            Some(quote! { <#field_ty as ::field_types::FieldTypes>::Value })
+        } else {
+            None
+        })
+        .collect::<Vec<_>>();
+    let offsets = get_field_offsets(fields.iter()
+        .map(|FieldInfo { ty: field_ty, flatten, .. }| if *flatten {
+            Some(quote! { <#field_ty as ::field_types::FieldTypes>::COUNT })
+        } else {
+            None
+        }));
+
+    let mut ser_where_clause = where_clause.cloned()
+        .unwrap_or_else(|| syn::parse_quote! { where });
+    let mut de_where_clause = ser_where_clause.clone();
+    for (FieldInfo { ty: field_ty, .. }, child_value) in fields.iter().zip(&child_values) {
+        match child_value {
+            Some(child_value) => {
+                ser_where_clause.predicates.push(syn::parse_quote! {
+                    #child_value: ::field_types::SerializeFieldType
+                });
+                de_where_clause.predicates.push(syn::parse_quote! {
+                    #child_value: ::field_types::DeserializeFieldType<'de>
+                });
+            },
+            None => {
+                ser_where_clause.predicates.push(syn::parse_quote! {
+                    #field_ty: ::field_types::__private::serde::Serialize
+                });
+                de_where_clause.predicates.push(syn::parse_quote! {
+                    #field_ty: ::field_types::__private::serde::Deserialize<'de>
+                });
+            },
+        }
+    }
+
+    let names_table = get_names_table(fields, &get_field_type_child_names(fields), offsets.last()
+        .expect("FieldType can only be derived for non-empty structures"));
+
+    let serialize_indices = fields.iter()
+        .zip(&offsets)
+        .map(|(FieldInfo { variant: variant_ident, flatten, .. }, offset)| {
+            if *flatten {
+                quote! {
+                    #enum_ty::#variant_ident(ref value) => #offset + ::field_types::SerializeFieldType::index(value)
+                }
+            } else {
+                quote! {
+                    #enum_ty::#variant_ident(_) => #offset
+                }
+            }
+        });
+
+    let serialize_values = fields.iter()
+        .map(|FieldInfo { variant: variant_ident, flatten, .. }| {
+            if *flatten {
+                quote! {
+                    #enum_ty::#variant_ident(ref value) => ::field_types::SerializeFieldType::serialize_value(value, map)
+                }
+            } else {
+                quote! {
+                    #enum_ty::#variant_ident(ref value) =>
+                        ::field_types::__private::serde::ser::SerializeMap::serialize_value(map, value)
+                }
+            }
+        });
+
+    let variant_indices = fields.iter()
+        .zip(&child_values)
+        .zip(&offsets)
+        .map(|((FieldInfo { ident: field_ident, prefix, .. }, child_value), offset)| {
+            match child_value {
+                Some(child_value) => {
+                    let child_index = quote! {
+                        if let ::core::option::Option::Some(index) =
+                            <#child_value as ::field_types::DeserializeFieldType<'de>>::variant_index(name)
+                        {
+                            return ::core::option::Option::Some(#offset + index);
+                        }
+                    };
+                    match prefix {
+                        Some(prefix) => quote! {
+                            if let ::core::option::Option::Some(name) = ::core::primitive::str::strip_prefix(name, #prefix) {
+                                #child_index
+                            }
+                        },
+                        None => child_index,
+                    }
+                },
+                None => {
+                    let field_name = field_ident.to_string();
+                    quote! {
+                        if name == #field_name { return ::core::option::Option::Some(#offset) }
+                    }
+                },
+            }
+        });
+
+    let deserialize_variants = fields.iter()
+        .zip(&child_values)
+        .zip(&offsets)
+        .map(|((FieldInfo { ty: field_ty, variant: variant_ident, .. }, child_value), offset)| {
+            match child_value {
+                Some(child_value) => quote! {
+                    if (#offset..#offset + <#child_value as ::field_types::DeserializeFieldType<'de>>::variant_count())
+                        .contains(&index)
+                    {
+                        return <#child_value as ::field_types::DeserializeFieldType<'de>>::deserialize_variant(index - #offset, map)
+                            .map(#enum_ty::#variant_ident);
+                    }
+                },
+                None => quote! {
+                    if index == #offset {
+                        return ::field_types::__private::serde::de::MapAccess::next_value::<#field_ty>(map)
+                            .map(#enum_ty::#variant_ident);
+                    }
+                },
+            }
+        });
+
+    let variant_count = offsets.last()
+        .expect("FieldType can only be derived for non-empty structures");
+
+    quote! {
+        impl #impl_generics ::field_types::SerializeFieldType for #enum_ty #ty_generics
+            #ser_where_clause
+        {
+            fn index(&self) -> usize {
+                match *self {
+                    #(#serialize_indices),*
+                }
+            }
+
+            fn name(&self) -> &'static str {
+                const NAMES: &[&str] = #names_table;
+                NAMES[::field_types::SerializeFieldType::index(self)]
+            }
+
+            fn serialize_value<M>(&self, map: &mut M) -> ::core::result::Result<(), M::Error>
+                where M: ::field_types::__private::serde::ser::SerializeMap
+            {
+                match *self {
+                    #(#serialize_values),*
+                }
+            }
+        }
+
+        impl #impl_generics ::field_types::__private::serde::Serialize for #enum_ty #ty_generics
+            #ser_where_clause
+        {
+            fn serialize<S>(&self, serializer: S) -> ::core::result::Result<S::Ok, S::Error>
+                where S: ::field_types::__private::serde::Serializer
+            {
+                ::field_types::__private::serialize_field_type(self, serializer)
+            }
+        }
+
+        impl #de_impl_generics ::field_types::DeserializeFieldType<'de> for #enum_ty #ty_generics
+            #de_where_clause
+        {
+            fn variant_count() -> usize {
+                #variant_count
+            }
+
+            fn variant_index(name: &str) -> ::core::option::Option<usize> {
+                #(#variant_indices)*
+                ::core::option::Option::None
+            }
+
+            fn variant_name(index: usize) -> ::core::option::Option<&'static str> {
+                const NAMES: &[&str] = #names_table;
+                if index < NAMES.len() {
+                    ::core::option::Option::Some(NAMES[index])
+                } else {
+                    ::core::option::Option::None
+                }
+            }
+
+            fn deserialize_variant<A>(index: usize, map: &mut A) -> ::core::result::Result<Self, A::Error>
+                where A: ::field_types::__private::serde::de::MapAccess<'de>
+            {
+                #(#deserialize_variants)*
+                ::core::result::Result::Err(::field_types::__private::serde::de::Error::custom("invalid field index"))
+            }
+        }
+
+        impl #de_impl_generics ::field_types::__private::serde::Deserialize<'de> for #enum_ty #ty_generics
+            #de_where_clause
+        {
+            fn deserialize<D>(deserializer: D) -> ::core::result::Result<Self, D::Error>
+                where D: ::field_types::__private::serde::Deserializer<'de>
+            {
+                ::field_types::__private::deserialize_field_type(deserializer)
+            }
+        }
+    }
+}
+
+/// Generates `Index`/`IndexMut` by the `..FieldName` enum and conversions to/from an array
+/// for the struct which fields have the same type.
+fn get_index_impl(
+    ty: &Ident,
+    generics: &Generics,
+    vis: &Visibility,
+    enum_ty: &Ident,
+    struct_fields: &Fields,
+    fields: &[FieldInfo],
+) -> TokenStream2 {
+    let field_ty = &fields[0].ty;
+    let field_ty_str = field_ty.to_token_stream().to_string();
+    if let Some(field) = fields.iter().find(|field| field.ty.to_token_stream().to_string() != field_ty_str) {
+        panic!("Option `index` requires the same type of all fields, but field `{}` has type `{}` instead of `{}`.",
+               field.ident, field.ty.to_token_stream(), field_ty_str);
+    }
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let fields_count = fields.len();
+
+    let field_refs = fields.iter()
+        .map(|FieldInfo { ident: field_ident, variant: variant_ident, .. }| {
+            quote! {
+                #enum_ty::#variant_ident => &self.#field_ident
+            }
+        });
+
+    let field_muts = fields.iter()
+        .map(|FieldInfo { ident: field_ident, variant: variant_ident, .. }| {
+            quote! {
+                #enum_ty::#variant_ident => &mut self.#field_ident
+            }
+        });
+
+    let fields_idents = fields.iter()
+        .map(|FieldInfo { ident: field_ident, .. }| field_ident)
+        .collect::<Vec<_>>();
+
+    let skipped_fields = struct_fields.iter()
+        .filter(|field| field.ident.as_ref().is_some_and(|field_ident| !fields_idents.contains(&field_ident)))
+        .collect::<Vec<_>>();
+    let skipped_fields_idents = skipped_fields.iter()
+        .map(|field| &field.ident);
+    // The bounds are higher-ranked, so a non-generic skipped field without `Default` only disables `from_array`
+    let skipped_fields_bounds = skipped_fields.iter()
+        .map(|field| {
+            let field_ty = &field.ty;
+            quote! { for<'__skipped> #field_ty: ::core::default::Default }
+        });
+
+    quote! {
+        impl #impl_generics ::core::ops::Index<#enum_ty> for #ty #ty_generics
+            #where_clause
+        {
+            type Output = #field_ty;
+
+            fn index(&self, name: #enum_ty) -> &#field_ty {
+                match name {
+                    #(#field_refs),*
+                }
+            }
+        }
+
+        impl #impl_generics ::core::ops::IndexMut<#enum_ty> for #ty #ty_generics
+            #where_clause
+        {
+            fn index_mut(&mut self, name: #enum_ty) -> &mut #field_ty {
+                match name {
+                    #(#field_muts),*
+                }
+            }
+        }
+
+        impl #impl_generics #ty #ty_generics
+            #where_clause
+        {
+            #vis fn into_array(self) -> [#field_ty; #fields_count] {
+                [#(self.#fields_idents),*]
+            }
+
+            #vis fn from_array(array: [#field_ty; #fields_count]) -> Self
+                where #(#skipped_fields_bounds),*
+            {
+                let [#(#fields_idents),*] = array;
+                #ty {
+                    #(#fields_idents,)*
+                    #(#skipped_fields_idents: ::core::default::Default::default(),)*
+                }
+            }
+        }
+    }
+}
+
+/// Generates field-wise `map`/`zip_with` methods for the struct with a single type parameter,
+/// which is the type of all (non-skipped) fields.
+fn get_map_impl(
+    ty: &Ident,
+    generics: &Generics,
+    vis: &Visibility,
+    enum_ty: &Ident,
+    struct_fields: &Fields,
+    fields: &[FieldInfo],
+) -> TokenStream2 {
+    let param = match (generics.params.len(), generics.params.first()) {
+        (1, Some(GenericParam::Type(param))) => param,
+        _ => panic!("Option `map` requires the struct with a single type parameter."),
+    };
+    if generics.where_clause.is_some() {
+        panic!("Option `map` doesn't support `where` clause, use bounds of the type parameter instead.");
+    }
+
+    let param_ident = &param.ident;
+    let param_name = param_ident.to_string();
+    let mut bound_names = GenericNames::default();
+    for bound in &param.bounds {
+        bound_names.visit_type_param_bound(bound);
+    }
+    if bound_names.0.contains(&param_name) {
+        panic!("Option `map` doesn't support bounds of the type parameter `{}` which refer to itself.", param_name);
+    }
+
+    if let Some(field) = fields.iter().find(|field| field.ty.to_token_stream().to_string() != param_name) {
+        panic!("Option `map` requires the type `{}` of all fields, but field `{}` has type `{}`.",
+               param_name, field.ident, field.ty.to_token_stream());
+    }
+
+    let fields_idents = fields.iter()
+        .map(|FieldInfo { ident: field_ident, .. }| field_ident)
+        .collect::<Vec<_>>();
+
+    let skipped_fields_idents = struct_fields.iter()
+        .filter(|field| field.ident.as_ref().is_some_and(|field_ident| !fields_idents.contains(&field_ident)))
+        .map(|field| {
+            let mut names = GenericNames::default();
+            names.visit_type(&field.ty);
+            if names.0.contains(&param_name) {
+                panic!("Option `map` requires the skipped field `{}` not to depend on the type `{}`.",
+                       field.ident.as_ref().unwrap(), param_name);
+            }
+            field.ident.as_ref().unwrap()
+        })
+        .collect::<Vec<_>>();
+
+    let fresh_ident = |candidates: &[&str]| candidates.iter()
+        .find(|candidate| **candidate != param_name)
+        .map(|candidate| Ident::new(candidate, Span::call_site()))
+        .unwrap();
+    let mapped_ident = fresh_ident(&["U", "V"]);
+    let result_ident = fresh_ident(&["R", "S"]);
+    let bounds = &param.bounds;
+
+    let mapped_fields = fields.iter()
+        .map(|FieldInfo { ident: field_ident, variant: variant_ident, .. }| {
+            quote! {
+                #field_ident: f(#enum_ty::#variant_ident, self.#field_ident)
+            }
+        });
+
+    let zipped_fields = fields.iter()
+        .map(|FieldInfo { ident: field_ident, variant: variant_ident, .. }| {
+            quote! {
+                #field_ident: f(#enum_ty::#variant_ident, self.#field_ident, other.#field_ident)
+            }
+        });
+
+    let (impl_generics, ty_generics, _) = generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics #ty #ty_generics {
+            #vis fn map<#mapped_ident: #bounds>(
+                self,
+                mut f: impl ::core::ops::FnMut(#enum_ty, #param_ident) -> #mapped_ident,
+            ) -> #ty<#mapped_ident> {
+                #ty {
+                    #(#mapped_fields,)*
+                    #(#skipped_fields_idents: self.#skipped_fields_idents,)*
+                }
+            }
+
+            #vis fn zip_with<#mapped_ident: #bounds, #result_ident: #bounds>(
+                self,
+                other: #ty<#mapped_ident>,
+                mut f: impl ::core::ops::FnMut(#enum_ty, #param_ident, #mapped_ident) -> #result_ident,
+            ) -> #ty<#result_ident> {
+                #ty {
+                    #(#zipped_fields,)*
+                    #(#skipped_fields_idents: self.#skipped_fields_idents,)*
+                }
+            }
+        }
+    }
+}
+
+/// Generates `for_each_field`/`for_each_field_mut` methods, which visit the fields as trait objects
+/// of the generated `..FieldVisit` trait with the given supertraits.
+fn get_visit_impl(
+    ty: &Ident,
+    generics: &Generics,
+    vis: &Visibility,
+    enum_ty: &Ident,
+    traits: &[Path],
+    fields: &[FieldInfo],
+) -> TokenStream2 {
+    let visit_trait = Ident::new(&(ty.to_string() + "FieldVisit"), Span::call_site());
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let field_visits = fields.iter()
+        .map(|FieldInfo { ident: field_ident, variant: variant_ident, .. }| {
+            quote! {
+                f(#enum_ty::#variant_ident, &self.#field_ident);
+            }
+        });
+
+    let field_visits_mut = fields.iter()
+        .map(|FieldInfo { ident: field_ident, variant: variant_ident, .. }| {
+            quote! {
+                f(#enum_ty::#variant_ident, &mut self.#field_ident);
+            }
+        });
+
+    let field_bounds = fields.iter()
+        .map(|FieldInfo { ty: field_ty, .. }| {
+            quote! {
+                #field_ty: #visit_trait
+            }
+        })
+        .collect::<Vec<_>>();
+
+    quote! {
+        #vis trait #visit_trait: #(#traits)+* {}
+
+        impl<T: ?::core::marker::Sized + #(#traits)+*> #visit_trait for T {}
+
+        impl #impl_generics #ty #ty_generics
+            #where_clause
+        {
+            #vis fn for_each_field(&self, mut f: impl ::core::ops::FnMut(#enum_ty, &dyn #visit_trait))
+                where #(#field_bounds),*
+            {
+                #(#field_visits)*
+            }
+
+            #vis fn for_each_field_mut(&mut self, mut f: impl ::core::ops::FnMut(#enum_ty, &mut dyn #visit_trait))
+                where #(#field_bounds),*
+            {
+                #(#field_visits_mut)*
+            }
+        }
+    }
+}
+
+/// Generates `get_any`/`get_any_mut`/`set_any` methods for the dynamic access to the fields as `dyn Any`.
+fn get_any_impl(
+    ty: &Ident,
+    generics: &Generics,
+    vis: &Visibility,
+    enum_ty: &Ident,
+    fields: &[FieldInfo],
+) -> TokenStream2 {
+    if generics.lifetimes().next().is_some() {
+        panic!("Option `any` requires the struct without lifetime parameters.");
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let field_refs = fields.iter()
+        .map(|FieldInfo { ident: field_ident, variant: variant_ident, .. }| {
+            quote! {
+                #enum_ty::#variant_ident => &self.#field_ident
+            }
+        });
+
+    let field_muts = fields.iter()
+        .map(|FieldInfo { ident: field_ident, variant: variant_ident, .. }| {
+            quote! {
+                #enum_ty::#variant_ident => &mut self.#field_ident
+            }
+        });
+
+    let field_bounds = fields.iter()
+        .map(|FieldInfo { ty: field_ty, .. }| {
+            quote! {
+                #field_ty: 'static
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let set_any = if cfg!(feature = "alloc") {
+        let field_sets = fields.iter()
+            .map(|FieldInfo { ident: field_ident, ty: field_ty, variant: variant_ident, .. }| {
+                let field_name = field_ident.to_string();
+                quote! {
+                    #enum_ty::#variant_ident => match value.downcast::<#field_ty>() {
+                        ::core::result::Result::Ok(value) => self.#field_ident = *value,
+                        ::core::result::Result::Err(_) => return ::core::result::Result::Err(
+                            ::field_types::TypeMismatch::new(#field_name, ::core::any::type_name::<#field_ty>())
+                        ),
+                    }
+                }
+            });
+
+        quote! {
+            #vis fn set_any(
+                &mut self,
+                name: #enum_ty,
+                value: ::field_types::__private::Box<dyn ::core::any::Any>,
+            ) -> ::core::result::Result<(), ::field_types::TypeMismatch>
+                where #(#field_bounds),*
+            {
+                match name {
+                    #(#field_sets),*
+                }
+                ::core::result::Result::Ok(())
+            }
+        }
+    } else {
+        // `set_any` takes a `Box`, so without `alloc` feature only the getters are generated, as documented
+        quote! {}
+    };
+
+    quote! {
+        impl #impl_generics #ty #ty_generics
+            #where_clause
+        {
+            #vis fn get_any(&self, name: #enum_ty) -> &dyn ::core::any::Any
+                where #(#field_bounds),*
+            {
+                match name {
+                    #(#field_refs),*
+                }
+            }
+
+            #vis fn get_any_mut(&mut self, name: #enum_ty) -> &mut dyn ::core::any::Any
+                where #(#field_bounds),*
+            {
+                match name {
+                    #(#field_muts),*
+                }
+            }
+
+            #set_any
+        }
+    }
+}
+
+/// Generates `..._fields` module with a marker type implementing `Field` trait for every field.
+fn get_field_keys_impl(
+    ty: &Ident,
+    generics: &Generics,
+    vis: &Visibility,
+    fields: &[FieldInfo],
+) -> TokenStream2 {
+    let mod_ident = Ident::new(&(ty.to_string().to_snake_case() + "_fields"), Span::call_site());
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    // The markers have the generic parameters of the struct without bounds and defaults
+    let marker_params = generics.params.iter()
+        .map(|param| match param {
+            GenericParam::Type(param) => {
+                let ident = &param.ident;
+                quote! { #ident: ?::core::marker::Sized }
+            },
+            GenericParam::Lifetime(param) => {
+                let lifetime = &param.lifetime;
+                quote! { #lifetime }
+            },
+            GenericParam::Const(param) => {
+                let (ident, ty) = (&param.ident, &param.ty);
+                quote! { const #ident: #ty }
+            },
+        })
+        .collect::<Vec<_>>();
+    let marker_args = generics.params.iter()
+        .map(|param| match param {
+            GenericParam::Type(param) => param.ident.to_token_stream(),
+            GenericParam::Lifetime(param) => param.lifetime.to_token_stream(),
+            GenericParam::Const(param) => param.ident.to_token_stream(),
+        })
+        .collect::<Vec<_>>();
+    let phantom_types = generics.params.iter()
+        .filter_map(|param| match param {
+            GenericParam::Type(param) => {
+                let ident = &param.ident;
+                Some(quote! { *const #ident })
+            },
+            GenericParam::Lifetime(param) => {
+                let lifetime = &param.lifetime;
+                Some(quote! { &#lifetime () })
+            },
+            GenericParam::Const(_) => None,
+        })
+        .collect::<Vec<_>>();
+    let (marker_body, marker_value) = if phantom_types.is_empty() {
+        (quote! { ; }, quote! { Self })
+    } else {
+        (
+            quote! { (pub ::core::marker::PhantomData<fn() -> (#(#phantom_types,)*)>); },
+            quote! { Self(::core::marker::PhantomData) },
+        )
+    };
+
+    // A marker gives access to its field, so it is not more visible than the field or the struct
+    let markers = fields.iter()
+        .map(|FieldInfo { vis: field_vis, variant: variant_ident, docs, .. }| {
+            let marker_vis = get_nested_vis(if is_visible(field_vis, vis) { vis } else { field_vis });
+            quote! {
+                #(#docs)*
+                #marker_vis struct #variant_ident<#(#marker_params),*> #marker_body
+
+                impl<#(#marker_params),*> ::core::clone::Clone for #variant_ident<#(#marker_args),*> {
+                    fn clone(&self) -> Self {
+                        *self
+                    }
+                }
+
+                impl<#(#marker_params),*> ::core::marker::Copy for #variant_ident<#(#marker_args),*> {}
+
+                impl<#(#marker_params),*> ::core::default::Default for #variant_ident<#(#marker_args),*> {
+                    fn default() -> Self {
+                        #marker_value
+                    }
+                }
+            }
+        });
+
+    let field_impls = fields.iter()
+        .map(|FieldInfo { ident: field_ident, ty: field_ty, variant: variant_ident, .. }| {
+            let field_name = field_ident.to_string();
+            quote! {
+                impl #impl_generics ::field_types::Field for #mod_ident::#variant_ident #ty_generics
+                    #where_clause
+                {
+                    type Owner = #ty #ty_generics;
+                    type Type = #field_ty;
+
+                    const NAME: &'static str = #field_name;
+
+                    fn get(owner: &Self::Owner) -> &Self::Type {
+                        &owner.#field_ident
+                    }
+
+                    fn get_mut(owner: &mut Self::Owner) -> &mut Self::Type {
+                        &mut owner.#field_ident
+                    }
+                }
+            }
+        });
+
+    let doc = format!("Marker types of the fields of [`{}`], implementing `field_types::Field` trait.", ty);
+    quote! {
+        #[doc = #doc]
+        #vis mod #mod_ident {
+            #(#markers)*
+        }
+
+        #(#field_impls)*
+    }
+}
+
+/// Generates `json_patch` and `apply_merge_patch` methods, which use the field names as the JSON members.
+fn get_json_patch_impl(
+    ty: &Ident,
+    generics: &Generics,
+    vis: &Visibility,
+    enum_ty: &Ident,
+    fields: &[FieldInfo],
+) -> TokenStream2 {
+    if !cfg!(feature = "serde_json") {
+        panic!("Option `json_patch` requires `serde_json` feature of `field_types` crate.");
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let fields_idents = fields.iter()
+        .map(|FieldInfo { ident: field_ident, .. }| field_ident)
+        .collect::<Vec<_>>();
+    let fields_names = fields.iter()
+        .map(|FieldInfo { ident: field_ident, .. }| field_ident.to_string())
+        .collect::<Vec<_>>();
+    let field_variants = fields.iter()
+        .map(|FieldInfo { variant: variant_ident, .. }| variant_ident);
+
+    let serialize_bounds = fields.iter()
+        .map(|FieldInfo { ty: field_ty, .. }| quote! { #field_ty: ::field_types::__private::serde::Serialize });
+    let merge_bounds = fields.iter()
+        .map(|FieldInfo { ty: field_ty, .. }| quote! {
+            #field_ty: ::field_types::__private::serde::Serialize + ::field_types::__private::serde::de::DeserializeOwned
+        });
+
+    quote! {
+        impl #impl_generics #ty #ty_generics
+            #where_clause
+        {
+            #vis fn json_patch(&self, other: &Self)
+                -> ::core::result::Result<::field_types::__private::serde_json::Value, ::field_types::__private::serde_json::Error>
+                where #(#serialize_bounds),*
+            {
+                let mut ops = ::field_types::__private::Vec::new();
+                #(
+                    ::field_types::__private::push_replace_op(
+                        &mut ops, #fields_names, &self.#fields_idents, &other.#fields_idents,
+                    )?;
+                )*
+                ::core::result::Result::Ok(::field_types::__private::serde_json::Value::Array(ops))
+            }
+
+            #vis fn apply_merge_patch(&mut self, patch: &::field_types::__private::serde_json::Value)
+                -> ::core::result::Result<(), ::field_types::MergePatchError<#enum_ty>>
+                where #(#merge_bounds),*
+            {
+                let patch = match patch.as_object() {
+                    ::core::option::Option::Some(patch) => patch,
+                    ::core::option::Option::None => return ::core::result::Result::Err(::field_types::MergePatchError::NotAnObject),
+                };
+                for name in patch.keys() {
+                    if #(name != #fields_names)&&* {
+                        return ::core::result::Result::Err(
+                            ::field_types::MergePatchError::UnknownField(::core::clone::Clone::clone(name))
+                        );
+                    }
+                }
+                #(
+                    let #fields_idents = match patch.get(#fields_names) {
+                        ::core::option::Option::Some(value) => ::core::option::Option::Some(
+                            ::field_types::__private::merge_field(&self.#fields_idents, value)
+                                .map_err(|error| ::field_types::MergePatchError::Field {
+                                    field: #enum_ty::#field_variants,
+                                    error,
+                                })?
+                        ),
+                        ::core::option::Option::None => ::core::option::Option::None,
+                    };
+                )*
+                #(
+                    if let ::core::option::Option::Some(value) = #fields_idents {
+                        self.#fields_idents = value;
+                    }
+                )*
+                ::core::result::Result::Ok(())
+            }
+        }
+    }
+}
+
+/// Generates `..Tracked` wrapper of the struct, which records the fields changed by its setters.
+fn get_tracked_impl(
+    ty: &Ident,
+    generics: &Generics,
+    vis: &Visibility,
+    enum_ty: &Ident,
+    derives: &[Path],
+    fields: &[FieldInfo],
+) -> TokenStream2 {
+    check_field_name_set_limit("tracked", fields);
+    let tracked_ty = Ident::new(&(ty.to_string() + "Tracked"), Span::call_site());
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let derive = if derives.is_empty() {
+        quote! {}
+    } else {
+        quote! { #[derive(#(#derives),*)] }
+    };
+
+    let field_methods = fields.iter()
+        .map(|FieldInfo { vis: field_vis, ident: field_ident, ty: field_ty, variant: variant_ident, .. }| {
+            let setter = Ident::new(&format!("set_{}", field_ident), Span::call_site());
+            let getter_mut = Ident::new(&format!("{}_mut", field_ident), Span::call_site());
+            quote! {
+                #field_vis fn #setter(&mut self, value: #field_ty) -> #field_ty {
+                    self.changes.insert(#enum_ty::#variant_ident);
+                    ::core::mem::replace(&mut self.value.#field_ident, value)
+                }
+
+                #field_vis fn #getter_mut(&mut self) -> &mut #field_ty {
+                    self.changes.insert(#enum_ty::#variant_ident);
+                    &mut self.value.#field_ident
+                }
+            }
+        });
+
+    quote! {
+        #derive
+        #vis struct #tracked_ty #generics
+            #where_clause
+        {
+            value: #ty #ty_generics,
+            changes: ::field_types::FieldNameSet<#enum_ty>,
+        }
+
+        impl #impl_generics #tracked_ty #ty_generics
+            #where_clause
+        {
+            #vis fn new(value: #ty #ty_generics) -> Self {
+                #tracked_ty {
+                    value,
+                    changes: ::field_types::FieldNameSet::empty(),
+                }
+            }
+
+            #vis fn get(&self) -> &#ty #ty_generics {
+                &self.value
+            }
+
+            #vis fn into_inner(self) -> #ty #ty_generics {
+                self.value
+            }
+
+            #vis fn changes(&self) -> ::field_types::FieldNameSet<#enum_ty> {
+                self.changes
+            }
+
+            #vis fn take_changes(&mut self) -> ::field_types::FieldNameSet<#enum_ty> {
+                ::core::mem::replace(&mut self.changes, ::field_types::FieldNameSet::empty())
+            }
+
+            #(#field_methods)*
+        }
+
+        impl #impl_generics ::core::convert::From<#ty #ty_generics> for #tracked_ty #ty_generics
+            #where_clause
+        {
+            fn from(value: #ty #ty_generics) -> Self {
+                #tracked_ty::new(value)
+            }
+        }
+
+        impl #impl_generics ::core::ops::Deref for #tracked_ty #ty_generics
+            #where_clause
+        {
+            type Target = #ty #ty_generics;
+
+            fn deref(&self) -> &Self::Target {
+                &self.value
+            }
+        }
+    }
+}
+
+/// Panics if the option, which generates code using `FieldNameSet`, is used with more than 128 fields.
+fn check_field_name_set_limit(option: &str, fields: &[FieldInfo]) {
+    if fields.len() > 128 {
+        panic!("Option `{}` can't be used with more than 128 fields.", option);
+    }
+}
+
+/// Generates `..Partial` struct with optional fields, and conversions between the struct and the partial struct.
+fn get_partial_impl(
+    ty: &Ident,
+    generics: &Generics,
+    vis: &Visibility,
+    enum_ty: &Ident,
+    derives: &[Path],
+    struct_fields: &Fields,
+    fields: &[FieldInfo],
+) -> TokenStream2 {
+    check_field_name_set_limit("partial", fields);
+    let partial_ty = Ident::new(&(ty.to_string() + "Partial"), Span::call_site());
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let partial_generics = filter_generics(generics, fields);
+    let (partial_impl_generics, partial_ty_generics, partial_where_clause) = partial_generics.split_for_impl();
+
+    let derive = if derives.is_empty() {
+        quote! {}
+    } else {
+        quote! { #[derive(#(#derives),*)] }
+    };
+
+    let partial_fields = fields.iter()
+        .map(|FieldInfo { vis: field_vis, ident: field_ident, ty: field_ty, docs, .. }| {
+            quote! {
+                #(#docs)*
+                #field_vis #field_ident: ::core::option::Option<#field_ty>
+            }
+        });
+
+    let fields_idents = fields.iter()
+        .map(|FieldInfo { ident: field_ident, .. }| field_ident)
+        .collect::<Vec<_>>();
+
+    let skipped_fields = struct_fields.iter()
+        .filter(|field| field.ident.as_ref().is_some_and(|field_ident| !fields_idents.contains(&field_ident)))
+        .collect::<Vec<_>>();
+    let skipped_fields_idents = skipped_fields.iter()
+        .map(|field| &field.ident);
+
+    // The bounds are higher-ranked, so a non-generic skipped field without `Default` only disables `TryFrom`
+    let mut try_from_where_clause = where_clause.cloned()
+        .unwrap_or_else(|| syn::parse_quote! { where });
+    for field in &skipped_fields {
+        let field_ty = &field.ty;
+        try_from_where_clause.predicates.push(syn::parse_quote! { for<'__skipped> #field_ty: ::core::default::Default });
+    }
+
+    let field_variants = fields.iter()
+        .map(|FieldInfo { variant: variant_ident, .. }| variant_ident);
+
+    quote! {
+        #derive
+        #vis struct #partial_ty #partial_generics
+            #partial_where_clause
+        {
+            #(#partial_fields),*
+        }
+
+        impl #partial_impl_generics ::core::default::Default for #partial_ty #partial_ty_generics
+            #partial_where_clause
+        {
+            fn default() -> Self {
+                #partial_ty {
+                    #(#fields_idents: ::core::option::Option::None),*
+                }
+            }
+        }
+
+        impl #partial_impl_generics #partial_ty #partial_ty_generics
+            #partial_where_clause
+        {
+            #vis fn merge(self, other: Self) -> Self {
+                #partial_ty {
+                    #(#fields_idents: ::core::option::Option::or(other.#fields_idents, self.#fields_idents)),*
+                }
+            }
+
+            #vis fn is_empty(&self) -> bool {
+                #(::core::option::Option::is_none(&self.#fields_idents))&&*
+            }
+        }
+
+        impl #impl_generics #ty #ty_generics
+            #where_clause
+        {
+            #vis fn into_partial(self) -> #partial_ty #partial_ty_generics {
+                #partial_ty {
+                    #(#fields_idents: ::core::option::Option::Some(self.#fields_idents)),*
+                }
+            }
+
+            #vis fn apply(&mut self, partial: #partial_ty #partial_ty_generics) {
+                #(
+                    if let ::core::option::Option::Some(value) = partial.#fields_idents {
+                        self.#fields_idents = value;
+                    }
+                )*
+            }
+        }
+
+        impl #impl_generics ::core::convert::TryFrom<#partial_ty #partial_ty_generics> for #ty #ty_generics
+            #try_from_where_clause
+        {
+            type Error = ::field_types::MissingFields<#enum_ty>;
+
+            fn try_from(partial: #partial_ty #partial_ty_generics) -> ::core::result::Result<Self, Self::Error> {
+                match (#(partial.#fields_idents,)*) {
+                    (#(::core::option::Option::Some(#fields_idents),)*) => ::core::result::Result::Ok(#ty {
+                        #(#fields_idents,)*
+                        #(#skipped_fields_idents: ::core::default::Default::default(),)*
+                    }),
+                    (#(#fields_idents,)*) => {
+                        let mut missing = ::field_types::FieldNameSet::empty();
+                        #(
+                            if ::core::option::Option::is_none(&#fields_idents) {
+                                missing.insert(#enum_ty::#field_variants);
+                            }
+                        )*
+                        ::core::result::Result::Err(::field_types::MissingFields::new(missing))
+                    },
+                }
+            }
+        }
+    }
+}
+
+/// Generates `FieldPath` implementation for the `..FieldName` enum, `FieldPaths` implementation for the struct,
+/// and `..FieldPath` enum, if the struct has `nested` fields.
+fn get_path_impl(
+    ty: &Ident,
+    generics: &Generics,
+    vis: &Visibility,
+    ty_vis: &Visibility,
+    enum_ty: &Ident,
+    fields: &[FieldInfo],
+) -> TokenStream2 {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let has_nested = fields.iter().any(|field| field.nested);
+    let path_ty = if has_nested {
+        Ident::new(&(ty.to_string() + "FieldPath"), Span::call_site())
+    } else {
+        enum_ty.clone()
+    };
+
+    // The path can't be an associated type of the struct, if it is less visible than the struct
+    let field_paths_impl = if is_visible(vis, ty_vis) {
+        quote! {
+            impl #impl_generics ::field_types::FieldPaths for #ty #ty_generics
+                #where_clause
+            {
+                type Path = #path_ty;
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let field_name_path_impl = quote! {
+        impl ::field_types::FieldPath for #enum_ty {
+            fn write_path<W: ::core::fmt::Write + ?::core::marker::Sized>(&self, out: &mut W) -> ::core::fmt::Result {
+                out.write_str(self.name())
+            }
+
+            fn by_path(path: &str) -> ::core::option::Option<Self> {
+                Self::by_name(path)
+            }
+        }
+    };
+
+    if !has_nested {
+        return quote! {
+            #field_name_path_impl
+
+            #field_paths_impl
+        };
+    }
+
+    let generic_names = generics.params.iter()
+        .map(generic_param_name)
+        .chain(Some("Self".to_string()))
+        .collect::<HashSet<_>>();
+    let nested_path_tys = fields.iter()
+        .map(|FieldInfo { ident: field_ident, ty: field_ty, nested, .. }| {
+            if !nested {
+                return None;
+            }
+            let mut names = GenericNames::default();
+            names.visit_type(field_ty);
+            if !names.0.is_disjoint(&generic_names) {
+                panic!("Type of the `nested` field `{}` can't depend on the generic parameters of the struct.", field_ident);
+            }
+            Some(quote! { <#field_ty as ::field_types::FieldPaths>::Path })
+        })
+        .collect::<Vec<_>>();
+
+    let path_variants = fields.iter()
+        .zip(&nested_path_tys)
+        .map(|(FieldInfo { variant: variant_ident, docs, .. }, nested_path_ty)| {
+            match nested_path_ty {
+                Some(nested_path_ty) => quote! {
+                    #(#docs)*
+                    #variant_ident(#nested_path_ty)
+                },
+                None => quote! {
+                    #(#docs)*
+                    #variant_ident
+                },
+            }
+        });
+
+    let path_writes = fields.iter()
+        .map(|FieldInfo { ident: field_ident, variant: variant_ident, nested, .. }| {
+            let field_name = field_ident.to_string();
+            if *nested {
+                let prefix = field_name + ".";
+                quote! {
+                    #path_ty::#variant_ident(ref path) => {
+                        out.write_str(#prefix)?;
+                        ::field_types::FieldPath::write_path(path, out)
+                    }
+                }
+            } else {
+                quote! {
+                    #path_ty::#variant_ident => out.write_str(#field_name)
+                }
+            }
+        });
+
+    let path_by_paths = fields.iter()
+        .map(|FieldInfo { variant: variant_ident, nested, .. }| {
+            if *nested {
+                quote! {
+                    (#enum_ty::#variant_ident, ::core::option::Option::Some(rest)) =>
+                        ::field_types::FieldPath::by_path(rest).map(#path_ty::#variant_ident)
+                }
+            } else {
+                quote! {
+                    (#enum_ty::#variant_ident, ::core::option::Option::None) =>
+                        ::core::option::Option::Some(#path_ty::#variant_ident)
+                }
+            }
+        });
+
+    let path_field_names = fields.iter()
+        .map(|FieldInfo { variant: variant_ident, nested, .. }| {
+            if *nested {
+                quote! { #path_ty::#variant_ident(_) => #enum_ty::#variant_ident }
+            } else {
+                quote! { #path_ty::#variant_ident => #enum_ty::#variant_ident }
+            }
+        });
+
+    quote! {
+        #[derive(::core::fmt::Debug, ::core::cmp::PartialEq, ::core::cmp::Eq,
+                 ::core::clone::Clone, ::core::marker::Copy)]
+        #vis enum #path_ty {
+            #(#path_variants),*
+        }
+
+        impl #path_ty {
+            #vis fn name(&self) -> ::field_types::FieldPathName<'_, Self> {
+                ::field_types::FieldPathName(self)
+            }
+
+            #vis fn by_path(path: &str) -> ::core::option::Option<Self> {
+                <Self as ::field_types::FieldPath>::by_path(path)
+            }
+
+            #vis fn field_name(&self) -> #enum_ty {
+                match *self {
+                    #(#path_field_names),*
+                }
+            }
+        }
+
+        impl ::field_types::FieldPath for #path_ty {
+            fn write_path<W: ::core::fmt::Write + ?::core::marker::Sized>(&self, out: &mut W) -> ::core::fmt::Result {
+                match *self {
+                    #(#path_writes),*
+                }
+            }
+
+            fn by_path(path: &str) -> ::core::option::Option<Self> {
+                let (name, rest) = match path.split_once('.') {
+                    ::core::option::Option::Some((name, rest)) => (name, ::core::option::Option::Some(rest)),
+                    ::core::option::Option::None => (path, ::core::option::Option::None),
+                };
+                match (#enum_ty::by_name(name)?, rest) {
+                    #(#path_by_paths,)*
+                    _ => ::core::option::Option::None,
+                }
+            }
+        }
+
+        #field_name_path_impl
+
+        #field_paths_impl
+    }
+}
+
+#[derive(Default)]
+struct Options {
+    vis: Option<Visibility>,
+    visible_only: bool,
+    name: Option<Ident>,
+    array_method: Option<Ident>,
+    index: bool,
+    map: bool,
+    visit: Option<Vec<Path>>,
+    any: bool,
+    field_keys: bool,
+    serde: bool,
+    partial: Option<Vec<Path>>,
+    diff: bool,
+    json_patch: bool,
+    tracked: Option<Vec<Path>>,
+    history: bool,
+}
+
+/// The structure options implemented by `FieldName` derive.
+const FIELD_NAME_OPTIONS: &[&str] = &[
+    "vis", "visible_only", "name", "array_method", "index", "map", "visit", "any", "field_keys",
+    "serde", "partial", "diff", "json_patch", "tracked",
+];
+
+/// The structure options implemented by `FieldType` derive. The `tracked` wrapper is generated by `FieldName` derive,
+/// so `FieldType` derive extends it only with the shared `field_types` attribute.
+const FIELD_TYPE_OPTIONS: &[&str] = &[
+    "vis", "visible_only", "name", "array_method", "serde", "diff", "history",
+];
+
+/// Parses the structure options of the derive, which implements the `supported` options.
+/// The shared `field_types` attribute can also contain the options of the other derive.
+fn get_options(attrs: &[Attribute], attr_names: &[&str], supported: &[&str]) -> Options {
+    let mut options = Options::default();
+    for attr in attrs {
+        if !attr_names.iter().any(|attr_name| attr.path.is_ident(attr_name)) {
+            continue;
+        }
+
+        let nested = match attr.parse_meta() {
+            Ok(Meta::List(list)) => list.nested,
+            _ => panic!("Unknown structure attribute `{}`, only list of options allowed.", attr.into_token_stream()),
+        };
+
+        let is_shared = attr.path.is_ident("field_types");
+        for meta in nested {
+            let option = match meta {
+                NestedMeta::Meta(ref meta) => meta.path().get_ident().map(|ident| ident.to_string()),
+                NestedMeta::Lit(_) => None,
+            };
+            if let Some(ref option) = option {
+                let option = option.as_str();
+                let is_known = FIELD_NAME_OPTIONS.contains(&option) || FIELD_TYPE_OPTIONS.contains(&option);
+                if is_known && !is_shared && !supported.contains(&option) {
+                    panic!("Option `{}` is not supported by `{}` attribute.", option, attr.path.to_token_stream());
+                }
+            }
+            match meta {
+                NestedMeta::Meta(Meta::NameValue(ref name_value)) if name_value.path.is_ident("vis") => {
+                    let vis = get_option_str(name_value);
+                    options.vis = Some(syn::parse_str(&vis)
+                        .unwrap_or_else(|_| panic!("Invalid visibility `{}` of `vis` option.", vis)));
+                },
+                NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("visible_only") => {
+                    options.visible_only = true;
+                },
+                NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("index") => {
+                    options.index = true;
+                },
+                NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("map") => {
+                    options.map = true;
+                },
+                NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("any") => {
+                    options.any = true;
+                },
+                NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("field_keys") => {
+                    options.field_keys = true;
+                },
+                NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("serde") => {
+                    options.serde = true;
+                },
+                NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("json_patch") => {
+                    options.json_patch = true;
+                },
+                NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("diff") => {
+                    options.diff = true;
+                },
+                NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("partial") => {
+                    options.partial = Some(Vec::new());
+                },
+                NestedMeta::Meta(Meta::List(ref list)) if list.path.is_ident("partial") => {
+                    options.partial = Some(get_option_derives(list));
+                },
+                NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("history") => {
+                    options.history = true;
+                },
+                NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("tracked") => {
+                    options.tracked = Some(Vec::new());
+                },
+                NestedMeta::Meta(Meta::List(ref list)) if list.path.is_ident("tracked") => {
+                    options.tracked = Some(get_option_derives(list));
+                },
+                NestedMeta::Meta(Meta::List(ref list)) if list.path.is_ident("visit") => {
+                    let traits = list.nested.iter()
+                        .map(|nested| match nested {
+                            NestedMeta::Meta(Meta::Path(path)) => path.clone(),
+                            _ => panic!("Option `visit` must be a list of traits, e.g. `visit(Debug, Display)`."),
+                        })
+                        .collect::<Vec<_>>();
+                    if traits.is_empty() {
+                        panic!("Option `visit` must contain at least one trait.");
+                    }
+                    options.visit = Some(traits);
+                },
+                NestedMeta::Meta(Meta::NameValue(ref name_value))
+                    if name_value.path.is_ident("name") || name_value.path.is_ident("array_method") =>
+                {
+                    if is_shared {
+                        panic!("Option `{}` can't be shared by both derives, use `field_name` or `field_type` attribute instead.",
+                               name_value.path.to_token_stream());
+                    }
+                    let name = get_option_str(name_value);
+                    let ident = Some(syn::parse_str(&name)
+                        .unwrap_or_else(|_| panic!("Invalid identifier `{}` of `{}` option.", name, name_value.path.to_token_stream())));
+                    if name_value.path.is_ident("name") {
+                        options.name = ident;
+                    } else {
+                        options.array_method = ident;
+                    }
+                },
+                _ => panic!("Unknown option `{}`.", meta.into_token_stream()),
+            }
+        }
+    }
+    options
+}
+
+fn get_option_str(name_value: &MetaNameValue) -> String {
+    match name_value.lit {
+        Lit::Str(ref value) => value.value(),
+        _ => panic!("Option `{}` must be a string.", name_value.path.to_token_stream()),
+    }
+}
+
+fn get_option_derives(list: &MetaList) -> Vec<Path> {
+    list.nested.iter()
+        .map(|nested| match nested {
+            NestedMeta::Meta(Meta::Path(path)) => path.clone(),
+            _ => panic!("Option `{0}` must be a list of derives, e.g. `{0}(Debug, Clone)`.", list.path.to_token_stream()),
+        })
+        .collect()
+}
+
+fn get_enum_derive(attrs: &[Attribute], derive_attr_names: &[&str], default: TokenStream2) -> TokenStream2 {
+    attrs.iter()
+        .filter_map(|attr| attr.parse_meta()
+            .ok()
+            .and_then(|meta| {
+                for attr_name in derive_attr_names {
+                    let attr_ident = Some(Ident::new(attr_name, Span::call_site()));
+                    let ident = meta.path().get_ident();
+                    if ident == attr_ident.as_ref() {
+                        if let Meta::List(meta_list) = &meta {
+                            let mut meta_list = meta_list.clone();
+                            meta_list.path = Path {
+                                leading_colon: None,
+                                segments: {
+                                    let mut segments = Punctuated::new();
+                                    segments.push(PathSegment {
+                                        ident: Ident::new("derive", Span::call_site()),
+                                        arguments: PathArguments::None,
+                                    });
+                                    segments
+                                }
+                            };
+                            return Some(meta_list);
+                        }
+                    }
+                }
+                None
+            })
+        )
+        .next()
+        .map(|meta_list| quote! { #[#meta_list] })
+        .unwrap_or(default)
+}
+
+struct FieldInfo {
+    vis: Visibility,
+    ident: Ident,
+    ty: Type,
+    variant: Ident,
+    docs: Vec<Attribute>,
+    nested: bool,
+    flatten: bool,
+    prefix: Option<String>,
+}
+
+fn filter_fields(fields: &Fields, skip_attr_name: &str) -> Vec<FieldInfo> {
+    fields.iter()
+        .filter_map(|field| {
+            let attr_values = field.attrs.iter()
+                .filter_map(|attr| get_field_attr_value(attr, &["field_types", skip_attr_name]))
+                .collect::<Vec<_>>();
+            match field.ident {
+                Some(ref field_ident) if !attr_values.contains(&FieldAttr::Skip) => {
+                    let field_name = field_ident.to_string();
+                    let variant_ident = Ident::new(&field_name.to_camel_case(), Span::call_site());
+                    let docs = field.attrs.iter()
+                        .filter(|attr| attr.path.is_ident("doc"))
+                        .cloned()
+                        .collect();
+                    Some(FieldInfo {
+                        vis: field.vis.clone(),
+                        ident: field_ident.clone(),
+                        ty: field.ty.clone(),
+                        variant: variant_ident,
+                        docs,
+                        nested: attr_values.contains(&FieldAttr::Nested),
+                        flatten: attr_values.iter().any(|value| matches!(value, FieldAttr::Flatten(_))),
+                        prefix: attr_values.iter()
+                            .find_map(|value| match value {
+                                FieldAttr::Flatten(prefix) => prefix.clone(),
+                                _ => None,
+                            }),
+                    })
+                },
+                _ => None,
+            }
+        })
+        .collect::<Vec<_>>()
+}
+
+/// Returns the expressions of the names of the `..FieldType` variants of `flatten` fields.
+fn get_field_type_child_names(fields: &[FieldInfo]) -> Vec<Option<TokenStream2>> {
+    fields.iter()
+        .map(|FieldInfo { ty: field_ty, flatten, .. }| if *flatten {
+            Some(quote! { <#field_ty as ::field_types::FieldTypes>::NAMES })
+        } else {
+            None
+        })
+        .collect()
+}
+
+/// Returns the names of the variants as `&'static [&'static str]` expression. The names of the variants
+/// of `flatten` fields are the `child_names` expressions, which are prefixed at compile time.
+fn get_names_table(fields: &[FieldInfo], child_names: &[Option<TokenStream2>], count: &TokenStream2) -> TokenStream2 {
+    if child_names.iter().all(Option::is_none) {
+        let field_names = fields.iter()
+            .map(|FieldInfo { ident: field_ident, .. }| field_ident.to_string());
+        return quote! { &[#(#field_names),*] };
+    }
+    let lists = fields.iter()
+        .zip(child_names)
+        .map(|(FieldInfo { ident: field_ident, prefix, .. }, child_names)| {
+            match (child_names, prefix) {
+                (Some(child_names), Some(prefix)) => quote! {
+                    {
+                        const CHILD_NAMES: &[&str] = #child_names;
+                        const PREFIXED_BYTES: [u8; ::field_types::__private::prefixed_len(#prefix, CHILD_NAMES)] =
+                            ::field_types::__private::prefixed_bytes(#prefix, CHILD_NAMES);
+                        const PREFIXED_NAMES: [&str; CHILD_NAMES.len()] =
+                            ::field_types::__private::prefixed_names(&PREFIXED_BYTES, #prefix, CHILD_NAMES);
+                        &PREFIXED_NAMES
+                    }
+                },
+                (Some(child_names), None) => quote! { #child_names },
+                (None, _) => {
+                    let field_name = field_ident.to_string();
+                    quote! { &[#field_name] }
+                },
+            }
+        });
+    quote! {
+        {
+            const NAMES: [&str; #count] = ::field_types::__private::concat_names(&[#(#lists),*]);
+            &NAMES
+        }
+    }
+}
+
+/// Checks that the types of `flatten` fields don't depend on the generic parameters of the struct,
+/// and returns `true` if there are `flatten` fields.
+fn check_flatten_fields(generics: &Generics, fields: &[FieldInfo]) -> bool {
+    let generic_names = generics.params.iter()
+        .map(generic_param_name)
+        .chain(Some("Self".to_string()))
+        .collect::<HashSet<_>>();
+    for field in fields.iter().filter(|field| field.flatten) {
+        if field.nested {
+            panic!("Field `{}` can't be both `nested` and `flatten`.", field.ident);
+        }
+        let mut names = GenericNames::default();
+        names.visit_type(&field.ty);
+        if !names.0.is_disjoint(&generic_names) {
+            panic!("Type of the `flatten` field `{}` can't depend on the generic parameters of the struct.", field.ident);
+        }
+    }
+    fields.iter().any(|field| field.flatten)
+}
+
+/// Returns the position of the first variant for every field, followed by the number of all variants.
+/// The fields with `Some` count are flattened into that number of variants.
+fn get_field_offsets(counts: impl Iterator<Item = Option<TokenStream2>>) -> Vec<TokenStream2> {
+    let mut own = 0usize;
+    let mut flattened = Vec::new();
+    let offset = |own: usize, flattened: &[TokenStream2]| if flattened.is_empty() {
+        quote! { #own }
+    } else {
+        quote! { (#own #(+ #flattened)*) }
+    };
+
+    let mut offsets = Vec::new();
+    for count in counts {
+        offsets.push(offset(own, &flattened));
+        match count {
+            Some(count) => flattened.push(count),
+            None => own += 1,
+        }
+    }
+    offsets.push(offset(own, &flattened));
+    offsets
+}
+
+/// Returns the visibility for an item in a child module, which is equal to `vis` in the parent module.
+fn get_nested_vis(vis: &Visibility) -> Visibility {
+    let path = match vis {
+        Visibility::Public(_) | Visibility::Crate(_) => return vis.clone(),
+        Visibility::Inherited => return syn::parse_quote! { pub(super) },
+        Visibility::Restricted(restricted) => &restricted.path,
+    };
+    let first = path.segments.first().map(|segment| segment.ident.to_string());
+    let path: Path = match first.as_deref() {
+        Some("crate") => return vis.clone(),
+        Some("self") => {
+            let rest = path.segments.iter().skip(1);
+            syn::parse_quote! { super #(::#rest)* }
+        },
+        _ => syn::parse_quote! { super::#path },
+    };
+    syn::parse_quote! { pub(in #path) }
+}
+
+/// Checks that an item with `vis` visibility is at least as visible as an item with `target` visibility.
+fn is_visible(vis: &Visibility, target: &Visibility) -> bool {
+    fn rank(vis: &Visibility) -> u8 {
+        match vis {
+            Visibility::Public(_) => 3,
+            Visibility::Crate(_) => 2,
+            Visibility::Restricted(restricted) if restricted.path.is_ident("crate") => 2,
+            Visibility::Restricted(restricted) if restricted.path.is_ident("self") => 0,
+            Visibility::Restricted(_) => 1,
+            Visibility::Inherited => 0,
+        }
+    }
+
+    match (rank(vis), rank(target)) {
+        (1, 1) => vis.to_token_stream().to_string() == target.to_token_stream().to_string(),
+        (vis_rank, target_rank) => vis_rank >= target_rank,
+    }
+}
+
+/// Keeps only the generic parameters that are used by the types of the fields,
+/// so skipping a field never leaves an unused parameter in the generated enum.
+fn filter_generics(generics: &Generics, fields: &[FieldInfo]) -> Generics {
+    let mut used = GenericNames::default();
+    for field in fields {
+        used.visit_type(&field.ty);
+    }
+
+    let all = generics.params.iter()
+        .map(generic_param_name)
+        .collect::<HashSet<_>>();
+    let used = used.0.into_iter()
+        .filter(|name| all.contains(name))
+        .collect::<HashSet<_>>();
+    let uses_only_used = |visit: &dyn Fn(&mut GenericNames)| {
+        let mut names = GenericNames::default();
+        visit(&mut names);
+        names.0.iter().all(|name| !all.contains(name) || used.contains(name))
+    };
+
+    let mut filtered = generics.clone();
+    filtered.params = generics.params.iter()
+        .filter(|param| used.contains(&generic_param_name(param)))
+        .cloned()
+        .map(|mut param| {
+            match param {
+                GenericParam::Type(ref mut param) => {
+                    param.bounds = param.bounds.iter()
+                        .filter(|bound| uses_only_used(&|names| names.visit_type_param_bound(bound)))
+                        .cloned()
+                        .collect();
+                    if param.bounds.is_empty() {
+                        param.colon_token = None;
+                    }
+                    if let Some(ref default) = param.default {
+                        if !uses_only_used(&|names| names.visit_type(default)) {
+                            param.eq_token = None;
+                            param.default = None;
+                        }
+                    }
+                },
+                GenericParam::Lifetime(ref mut param) => {
+                    param.bounds = param.bounds.iter()
+                        .filter(|bound| used.contains(&bound.to_string()))
+                        .cloned()
+                        .collect();
+                    if param.bounds.is_empty() {
+                        param.colon_token = None;
+                    }
+                },
+                GenericParam::Const(_) => (),
+            }
+            param
+        })
+        .collect();
+
+    if let Some(ref mut where_clause) = filtered.where_clause {
+        where_clause.predicates = where_clause.predicates.iter()
+            .filter_map(|predicate| match predicate {
+                WherePredicate::Type(predicate) => {
+                    if !uses_only_used(&|names| names.visit_type(&predicate.bounded_ty)) {
+                        return None;
+                    }
+                    let mut predicate = predicate.clone();
+                    predicate.bounds = predicate.bounds.iter()
+                        .filter(|bound| uses_only_used(&|names| names.visit_type_param_bound(bound)))
+                        .cloned()
+                        .collect();
+                    if predicate.bounds.is_empty() {
+                        None
+                    } else {
+                        Some(WherePredicate::Type(predicate))
+                    }
+                },
+                WherePredicate::Lifetime(predicate) => {
+                    if !used.contains(&predicate.lifetime.to_string()) {
+                        return None;
+                    }
+                    let mut predicate = predicate.clone();
+                    predicate.bounds = predicate.bounds.iter()
+                        .filter(|bound| used.contains(&bound.to_string()))
+                        .cloned()
+                        .collect();
+                    if predicate.bounds.is_empty() {
+                        None
+                    } else {
+                        Some(WherePredicate::Lifetime(predicate))
+                    }
+                },
+                WherePredicate::Eq(_) => None,
+            })
+            .collect();
+    }
+    if filtered.where_clause.as_ref().is_some_and(|where_clause| where_clause.predicates.is_empty()) {
+        filtered.where_clause = None;
+    }
+
+    filtered
+}
+
+fn generic_param_name(param: &GenericParam) -> String {
+    match param {
+        GenericParam::Type(param) => param.ident.to_string(),
+        GenericParam::Lifetime(param) => param.lifetime.to_string(),
+        GenericParam::Const(param) => param.ident.to_string(),
+    }
+}
+
+/// Collects the lifetimes and the first segments of the relative paths,
+/// which are candidates for the names of the generic parameters.
+#[derive(Default)]
+struct GenericNames(HashSet<String>);
+
+impl<'ast> Visit<'ast> for GenericNames {
+    fn visit_lifetime(&mut self, lifetime: &'ast Lifetime) {
+        self.0.insert(lifetime.to_string());
+    }
+
+    fn visit_path(&mut self, path: &'ast Path) {
+        if path.leading_colon.is_none() {
+            if let Some(segment) = path.segments.first() {
+                self.0.insert(segment.ident.to_string());
+            }
+        }
+        visit::visit_path(self, path);
+    }
+}
+
+fn get_doc_text(docs: &[Attribute]) -> Option<String> {
+    let lines: Vec<_> = docs.iter()
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(Meta::NameValue(MetaNameValue { lit: Lit::Str(ref text), .. })) => Some(text.value()),
+            _ => None,
+        })
+        .flat_map(|text| text.split('\n')
+            .map(|line| line.strip_prefix(' ').unwrap_or(line).trim_end().to_string())
+            .collect::<Vec<_>>())
+        .collect();
+
+    let text = lines.join("\n");
+    let text = text.trim_matches('\n');
+    if text.is_empty() {
+        None
+    } else {
+        Some(text.to_string())
+    }
+}
+
+/// Formats the type as it is written in the source, e.g. `Option<&'a str>`,
+/// instead of the spaced tokens of `TokenStream::to_string`.
+fn type_to_string(ty: &Type) -> String {
+    fn is_word(token: &TokenTree) -> bool {
+        match token {
+            TokenTree::Ident(_) | TokenTree::Literal(_) => true,
+            TokenTree::Punct(punct) => punct.as_char() == '\'',
+            TokenTree::Group(_) => false,
+        }
+    }
+
+    fn write_tokens(tokens: TokenStream2, text: &mut String) {
+        let mut prev: Option<TokenTree> = None;
+        for token in tokens {
+            if prev.as_ref().is_some_and(is_word) && is_word(&token)
+                && !matches!(prev, Some(TokenTree::Punct(_)))
+            {
+                text.push(' ');
+            }
+            match token {
+                TokenTree::Group(ref group) => {
+                    let (open, close) = match group.delimiter() {
+                        Delimiter::Parenthesis => ("(", ")"),
+                        Delimiter::Bracket => ("[", "]"),
+                        Delimiter::Brace => ("{", "}"),
+                        Delimiter::None => ("", ""),
+                    };
+                    text.push_str(open);
+                    write_tokens(group.stream(), text);
+                    text.truncate(text.trim_end().len());
+                    text.push_str(close);
+                },
+                TokenTree::Punct(ref punct) => match punct.as_char() {
+                    ',' | ';' => {
+                        text.push(punct.as_char());
+                        text.push(' ');
+                    },
+                    '+' | '=' => {
+                        text.push(' ');
+                        text.push(punct.as_char());
+                        text.push(' ');
+                    },
+                    '-' => text.push_str(" -"),
+                    '>' if matches!(prev, Some(TokenTree::Punct(ref prev)) if prev.as_char() == '-') => {
+                        text.push_str("> ");
+                    },
+                    _ => text.push(punct.as_char()),
+                },
+                _ => text.push_str(&token.to_string()),
+            }
+            prev = Some(token);
+        }
+    }
+
+    let mut text = String::new();
+    write_tokens(ty.to_token_stream(), &mut text);
+    text.truncate(text.trim_end().len());
+    text
+}
+
+/// The value of the field attribute.
+#[derive(PartialEq)]
+enum FieldAttr {
+    Skip,
+    Nested,
+    /// Flattened field with the optional prefix of the names of its fields.
+    Flatten(Option<String>),
+}
+
+/// Returns `skip`, `nested` or `flatten` value of the field attribute with one of `attr_names`.
+fn get_field_attr_value(attr: &Attribute, attr_names: &[&str]) -> Option<FieldAttr> {
+    attr.parse_meta()
+        .ok()
+        .and_then(|meta| {
+            for attr_name in attr_names {
+                let attr_ident = Some(Ident::new(attr_name, Span::call_site()));
+                let ident = meta.path().get_ident();
+                if ident == attr_ident.as_ref() {
+                    return Some(meta);
+                }
+            }
+            None
+        })
+        .map(|meta| {
+            let value = match meta {
+                Meta::List(ref list) => match list.nested.first().expect("Attribute value can't be empty") {
+                    NestedMeta::Meta(Meta::List(ref list)) if list.path.is_ident("flatten") => {
+                        return FieldAttr::Flatten(Some(get_flatten_prefix(list)));
+                    },
+                    nested => nested.into_token_stream().to_string(),
+                },
+
+                Meta::NameValue(ref name_value) => name_value.lit
+                    .clone()
+                    .into_token_stream()
+                    .to_string(),
+
+                _ => panic!("Unknown attribute value, only `skip`, `nested` or `flatten` allowed."),
+            };
+            match value.trim_matches('"') {
+                "skip" => FieldAttr::Skip,
+                "nested" => FieldAttr::Nested,
+                "flatten" => FieldAttr::Flatten(None),
+                _ => panic!("Unknown attribute value `{}`, only `skip`, `nested` or `flatten` allowed.", value),
+            }
+        })
+}
+
+/// Returns the prefix of `flatten(prefix = "..")` field attribute.
+fn get_flatten_prefix(list: &MetaList) -> String {
+    match list.nested.iter().collect::<Vec<_>>().as_slice() {
+        [NestedMeta::Meta(Meta::NameValue(ref name_value))] if name_value.path.is_ident("prefix") => {
+            let prefix = get_option_str(name_value);
+            if prefix.is_empty() {
+                panic!("Prefix of `flatten` field can't be empty.");
+            }
+            prefix
+        },
+        _ => panic!("Unknown `flatten` attribute value, only `flatten(prefix = \"..\")` allowed."),
+    }
+}
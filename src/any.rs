@@ -0,0 +1,27 @@
+use core::fmt;
+
+/// The error of setting a field from a `dyn Any` value of another type.
+///
+/// This error is returned by `set_any` method, generated with `any` option.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TypeMismatch {
+    /// The name of the field.
+    pub field: &'static str,
+    /// The type name of the field.
+    pub expected: &'static str,
+}
+
+impl TypeMismatch {
+    #[doc(hidden)]
+    pub fn new(field: &'static str, expected: &'static str) -> Self {
+        Self { field, expected }
+    }
+}
+
+impl fmt::Display for TypeMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "type mismatch of field `{}`, expected value of type `{}`", self.field, self.expected)
+    }
+}
+
+impl core::error::Error for TypeMismatch {}
@@ -0,0 +1,150 @@
+use alloc::{collections::VecDeque, vec::Vec};
+use core::fmt;
+
+use crate::SetField;
+
+/// The undo/redo history of the changes of a struct, which stores the previous `..FieldType` values
+/// of the changed fields instead of the copies of the whole struct.
+///
+/// The changes between two checkpoints are undone and redone together. If the history has a capacity,
+/// the oldest checkpoints are dropped when the number of checkpoints exceeds it.
+///
+/// The `FieldType` derive generates the `..History` alias for this type with `history` option.
+pub struct History<T: SetField> {
+    undo: VecDeque<Vec<T::Value>>,
+    redo: Vec<Vec<T::Value>>,
+    changes: Vec<T::Value>,
+    capacity: Option<usize>,
+}
+
+impl<T: SetField> History<T> {
+    /// Creates the history without a capacity.
+    pub fn new() -> Self {
+        Self {
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            changes: Vec::new(),
+            capacity: None,
+        }
+    }
+
+    /// Creates the history, which keeps no more than `capacity` checkpoints to undo.
+    /// The history with zero capacity records nothing to undo.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            capacity: Some(capacity),
+            ..Self::new()
+        }
+    }
+
+    /// Returns the maximal number of checkpoints to undo, if any.
+    pub fn capacity(&self) -> Option<usize> {
+        self.capacity
+    }
+
+    /// Sets the field of the target to the value and records its previous value.
+    /// The changes, which were undone, can't be redone after it.
+    pub fn set(&mut self, target: &mut T, value: T::Value) {
+        let previous = target.set_field(value);
+        self.changes.push(previous);
+        self.redo.clear();
+    }
+
+    /// Finishes the current checkpoint, so the following changes are undone separately.
+    pub fn checkpoint(&mut self) {
+        if self.changes.is_empty() {
+            return;
+        }
+        let changes = core::mem::take(&mut self.changes);
+        self.undo.push_back(changes);
+        if let Some(capacity) = self.capacity {
+            while self.undo.len() > capacity {
+                self.undo.pop_front();
+            }
+        }
+    }
+
+    /// Reverts the changes of the target since the previous checkpoint.
+    /// Returns `false` if there is nothing to undo.
+    pub fn undo(&mut self, target: &mut T) -> bool {
+        self.checkpoint();
+        match self.undo.pop_back() {
+            Some(changes) => {
+                self.redo.push(Self::apply(target, changes));
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Reapplies the last undone changes of the target.
+    /// Returns `false` if there is nothing to redo.
+    pub fn redo(&mut self, target: &mut T) -> bool {
+        self.checkpoint();
+        match self.redo.pop() {
+            Some(changes) => {
+                self.undo.push_back(Self::apply(target, changes));
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Returns `true` if there are changes to undo.
+    pub fn can_undo(&self) -> bool {
+        // The current changes are dropped by `checkpoint`, if the history can't keep any checkpoint
+        (!self.changes.is_empty() && self.capacity != Some(0)) || !self.undo.is_empty()
+    }
+
+    /// Returns `true` if there are undone changes to redo.
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Forgets all recorded changes.
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+        self.changes.clear();
+    }
+
+    /// Sets the values in the reverse order and returns the replaced values, so applying them reverts the changes.
+    fn apply(target: &mut T, changes: Vec<T::Value>) -> Vec<T::Value> {
+        changes.into_iter()
+            .rev()
+            .map(|value| target.set_field(value))
+            .collect()
+    }
+}
+
+impl<T: SetField> Default for History<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: SetField> Clone for History<T>
+    where T::Value: Clone
+{
+    fn clone(&self) -> Self {
+        Self {
+            undo: self.undo.clone(),
+            redo: self.redo.clone(),
+            changes: self.changes.clone(),
+            capacity: self.capacity,
+        }
+    }
+}
+
+impl<T: SetField> fmt::Debug for History<T>
+    where T::Value: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("History")
+            .field("undo", &self.undo)
+            .field("redo", &self.redo)
+            .field("changes", &self.changes)
+            .field("capacity", &self.capacity)
+            .finish()
+    }
+}
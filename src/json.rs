@@ -0,0 +1,89 @@
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{Map, Value};
+
+use crate::FieldName;
+
+/// The error of applying a JSON merge patch to a struct.
+///
+/// This error is returned by `apply_merge_patch` method, generated with `json_patch` option.
+#[derive(Debug)]
+pub enum MergePatchError<N> {
+    /// The patch is not a JSON object.
+    NotAnObject,
+    /// The patch has a member, which is not a (non-skipped) field of the struct.
+    UnknownField(String),
+    /// The value of the field can't be deserialized from the patched value.
+    Field {
+        field: N,
+        error: serde_json::Error,
+    },
+}
+
+impl<N: FieldName> fmt::Display for MergePatchError<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotAnObject => f.write_str("merge patch must be a JSON object"),
+            Self::UnknownField(name) => write!(f, "unknown field `{}` in merge patch", name),
+            Self::Field { field, error } => write!(f, "invalid value of field `{}`: {}", field.name(), error),
+        }
+    }
+}
+
+impl<N: FieldName + fmt::Debug> core::error::Error for MergePatchError<N> {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::Field { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Applies RFC 7396 JSON merge patch to the target value.
+pub fn merge_patch(target: &mut Value, patch: &Value) {
+    let patch = match patch {
+        Value::Object(patch) => patch,
+        _ => {
+            *target = patch.clone();
+            return;
+        },
+    };
+    if !target.is_object() {
+        *target = Value::Object(Map::new());
+    }
+    if let Value::Object(target) = target {
+        for (name, value) in patch {
+            if value.is_null() {
+                target.remove(name);
+            } else {
+                merge_patch(target.entry(name.as_str()).or_insert(Value::Null), value);
+            }
+        }
+    }
+}
+
+/// Returns the field value with the applied merge patch of the member.
+pub fn merge_field<T: Serialize + DeserializeOwned>(current: &T, patch: &Value) -> Result<T, serde_json::Error> {
+    if patch.is_object() {
+        let mut value = serde_json::to_value(current)?;
+        merge_patch(&mut value, patch);
+        serde_json::from_value(value)
+    } else {
+        T::deserialize(patch)
+    }
+}
+
+/// Adds RFC 6902 `replace` operation for the field, if the serialized values differ.
+pub fn push_replace_op<T: Serialize>(ops: &mut Vec<Value>, name: &str, old: &T, new: &T) -> Result<(), serde_json::Error> {
+    let (old, new) = (serde_json::to_value(old)?, serde_json::to_value(new)?);
+    if old != new {
+        let mut op = Map::new();
+        op.insert("op".into(), Value::from("replace"));
+        op.insert("path".into(), Value::from(alloc::format!("/{}", name)));
+        op.insert("value".into(), new);
+        ops.push(Value::Object(op));
+    }
+    Ok(())
+}
@@ -7,20 +7,165 @@ This crate provides `FieldName` and `FieldType` derive macros for deriving enums
 
  * Variants with UpperCamelCase unit type names corresponding to the snake_case field names of the struct
  * Skipping fields with `#[field_name(skip)]` or `#[field_types(skip)]` attributes
+ * Doc comments of the struct fields are forwarded to the corresponding variants
  * Specifying some derives for generated enums with `#[field_name_derive(..)]` or `#[field_types_derive(..)]` structure attributes.
-By default, `..FieldName` has derive `Debug`, `PartialEq`, `Eq`, `Clone` and `Copy`.
+   By default, `..FieldName` has derive `Debug`, `PartialEq`, `Eq`, `Clone` and `Copy`.
  * Associated function `as_field_name_array` that returns array of variants
+ * Associated function `field_names` that returns an iterator over the variants
  * `From`/`Into` convert the struct reference to an array of variants
  * `name`/`by_name` methods for convert enum variants to/from string representation field names
+ * `description` method that returns the doc comment of the corresponding field, if any
+ * `type_name` method that returns the type of the corresponding field as it is written in the struct, and
+   `type_id` method that returns its `TypeId`, if the type doesn't depend on the generic parameters of the struct
+ * [`FieldName`] trait implementation for the enum and [`FieldNames`] trait implementation for the struct
+ * `..FieldNameSet` alias for the [`FieldNameSet`] bitset of the enum variants (for enums up to 128 variants, larger enums fail to compile with the set)
+ * `..FieldNameMap<V>` alias for the [`FieldNameMap`] with a value of type `V` for every enum variant
+
+### Flattened fields
+
+The `#[field_types(flatten)]` field attribute (or `#[field_name(flatten)]`/`#[field_type(flatten)]`
+for one derive) includes the fields of the field's struct, which also derives the enums, into the enums
+of the parent struct, like `#[serde(flatten)]`:
+
+ * The flattened field is a variant with the enum of the field's struct, e.g. `TestFieldName::Header(HeaderFieldName::Id)`
+   and `TestFieldType::Header(HeaderFieldType::Id(..))`, which `name`/`by_name`/`index` delegate to the child enum,
+   so `"id"` is a name of the parent enum
+ * The arrays and iterators of the parent struct contain the variants for all fields of the flattened struct
+ * `#[field_types(flatten(prefix = "request_"))]` prefixes the names of the flattened fields, e.g. `"request_id"`.
+   The prefixed names are built at compile time, and the names of all variants are available as
+   [`FieldName::NAMES`] and [`FieldTypes::NAMES`]
+ * The type of the flattened field can't depend on the generic parameters of the struct
+ * The names of all variants must be unique, so a flattened field with the name of another field
+   fails to compile, unless the names are prefixed
+ * `index`, `map`, `visit`, `any`, `field_keys`, `partial`, `diff`, `json_patch` and `tracked` options
+   and `nested` fields can't be used with flattened fields
+
+### `..FieldPath` enum
+
+ * Generated if the struct has fields with `#[field_types(nested)]` or `#[field_name(nested)]` attributes,
+   which types also derive `FieldName`
+ * Unit variants for the fields, and variants with the path of the nested struct for the nested fields,
+   e.g. `TestFieldPath::Address(AddressFieldName::City)`
+ * `name` method, which returns the dot-separated path name (`"address.city"`) as [`FieldPathName`],
+   `by_path` method for the reverse conversion, and `field_name` method, which returns the first field of the path
+ * [`FieldPath`] trait implementation for the enum (and for any `..FieldName` enum) and [`FieldPaths`] trait
+   implementation for the struct
+ * The marker types of `field_keys` option implement [`Lens`] trait, and the lenses can be composed
+   for the typed access to the nested fields, e.g. `test_fields::Address.then(address_fields::City)`
 
 ### `..FieldType` enum
 
  * Variants with UpperCamelCase type names corresponding to the snake_case field names of the struct
-and with values corresponding to the value types of the struct fields
+   and with values corresponding to the value types of the struct fields
  * Skipping fields with `#[field_type(skip)]` or `#[field_types(skip)]` attributes
+ * Doc comments of the struct fields are forwarded to the corresponding variants
+ * Generic parameters of the struct that are used only by the skipped fields are omitted from the enum
  * Specifying some derives for generated enums with `#[field_type_derive(..)]` or `#[field_types_derive(..)]` structure attributes
  * Associated function `into_field_type_array` that convert the struct into an array of variants with field values
- * `Into` convert the struct into an array of variants with field values
+ * Method `into_field_types` and `IntoIterator` implementation that convert the struct into an iterator
+   over variants with field values
+ * `From`/`Into` convert the struct into an array of variants with field values
+ * [`FieldTypes`] trait implementation for the struct
+
+### Structure options
+
+Options are specified with `#[field_types(..)]` structure attribute for both derives,
+or with `#[field_name(..)]`/`#[field_type(..)]` for the corresponding derive only:
+
+ * `vis = "pub(crate)"` sets the visibility of the generated enums and methods,
+   by default the visibility of the struct is used. If the enum is less visible than the struct,
+   the [`FieldNames`]/[`FieldTypes`] traits are not implemented for the struct
+ * `visible_only` skips the fields that are less visible than the generated enum,
+   so private fields of a public struct are not exposed
+ * `name = "TestKey"` sets the name of the generated enum instead of `TestFieldName`/`TestFieldType`
+ * `array_method = "keys"` sets the name of the `as_field_name_array`/`into_field_type_array` method
+ * `index` generates `Index`/`IndexMut` implementations by the `..FieldName` enum and `into_array`/`from_array`
+   methods for the struct which (non-skipped) fields have the same type. The skipped fields are initialized
+   with `Default::default()` by `from_array`, which is available only if they implement `Default`
+ * `map` generates field-wise `map`/`zip_with` methods for the struct with a single type parameter `T`,
+   which is the type of all (non-skipped) fields, e.g. `map` converts `Test<T>` into `Test<U>`
+   with `FnMut(TestFieldName, T) -> U` function
+ * `visit(Debug, Display)` generates `for_each_field`/`for_each_field_mut` methods, which call a function
+   with `TestFieldName` and a reference to the field value as `dyn TestFieldVisit` trait object. The generated
+   `TestFieldVisit` trait has the listed traits as supertraits and is implemented for all their implementors
+ * `field_keys` generates `test_fields` module (for `Test` struct) with a marker type for every field,
+   e.g. `test_fields::SecondField`, which implements [`Field`] trait with the struct and field types,
+   so generic code can be written over statically typed fields. A marker is as visible as its field
+   (but not more visible than the struct)
+ * `serde` implements `Serialize`/`Deserialize` for the `..FieldName` enum as the field names strings,
+   e.g. `"second_field"` instead of the variant name `"SecondField"`, and the deserialization error lists
+   the valid names. For the `..FieldType` enum it implements them as single-entry maps,
+   e.g. `TestFieldType::SecondField(Some("x"))` as `{"second_field": "x"}`.
+   A `Vec` of the values can be (de)serialized as a sparse object with [`serde_sparse`] module
+   (requires `serde` feature)
+ * `partial` generates `TestPartial` struct with the same (non-skipped) fields wrapped in `Option`,
+   `into_partial`/`apply` methods of the struct, `merge` method of the partial struct (the fields of the argument
+   override the fields of `self`), and `TryFrom<TestPartial>` for the struct, which returns [`MissingFields`] error
+   with the fields that are not set. The skipped fields are initialized with `Default::default()`,
+   so `TryFrom` is implemented only if they implement `Default`.
+   The derives of the partial struct can be specified with `partial(Debug, Clone)`.
+   The struct can't have more than 128 (non-skipped) fields
+ * `diff` generates `changed_fields` method for `FieldName` derive, which returns [`FieldNameSet`] of the fields
+   that differ in two values of the struct, and `diff` method for `FieldType` derive, which returns an iterator
+   over `..FieldType` values of the changed fields of the other value. The (non-skipped) fields must implement
+   `PartialEq` (and `Clone` for `diff`). `FieldName` derive can't use it with more than 128 (non-skipped) fields
+ * `json_patch` generates `json_patch` method for `FieldName` derive, which returns a JSON Patch (RFC 6902)
+   with `replace` operations of the changed fields (e.g. `"/second_field"`), and `apply_merge_patch` method,
+   which applies a JSON Merge Patch (RFC 7396) field by field and returns [`MergePatchError`] with the failing
+   field. The patch is applied only if all the fields are valid (requires `serde_json` feature)
+ * `tracked` generates `TestTracked` wrapper of the struct with `set_..`/`.._mut` methods of the fields,
+   which record the changed fields, and `changes`/`take_changes` methods, which return [`FieldNameSet`]
+   of the fields changed (since the last `take_changes` call). With `FieldType` derive and the shared
+   `#[field_types(tracked)]` attribute, `take_changed_values` method returns `..FieldType` values
+   of the changed fields, except the fields skipped only by `FieldType` derive. The derives of the wrapper
+   can be specified with `tracked(Debug, Clone)`. The struct can't have more than 128 (non-skipped) fields
+ * `history` generates `TestHistory` alias of [`History`] for `FieldType` derive, which records the previous
+   `..FieldType` values of the fields set through it, and undoes/redoes the changes between checkpoints
+   (requires `alloc` feature). The struct implements [`SetField`] trait, so the fields must be as visible
+   as the struct (the other fields can be skipped with `visible_only`), and the flattened structs must have
+   `history` option too
+ * `any` generates `get_any`/`get_any_mut` methods, which return a field by `TestFieldName` as `dyn Any`,
+   and `set_any` method, which sets a field from `Box<dyn Any>` or returns [`TypeMismatch`] error
+   (`set_any` is generated only with `alloc` feature, the other methods are generated without it).
+   The struct can't have lifetime parameters and its type parameters must be `'static`
+
+The `name` and `array_method` options can be specified only with `#[field_name(..)]` or `#[field_type(..)]` attribute.
+The options, which only one of the derives implements, are rejected in the attribute of the other derive,
+e.g. `#[field_type(partial)]`, but can be specified in the shared `#[field_types(..)]` attribute.
+
+### Traits
+
+The traits implemented by the derives allow to write generic code over any struct with derived enums:
+
+```rust
+use field_types::{FieldName, FieldNames, FieldType, FieldTypes};
+
+#[derive(FieldName, FieldType)]
+#[field_type_derive(Debug)]
+struct Test {
+    first: i32,
+    second_field: Option<String>,
+}
+
+fn names<T: FieldNames>() -> Vec<&'static str> {
+    T::field_names().map(|name| name.name()).collect()
+}
+
+fn dump<T: FieldTypes>(source: T) -> Vec<String>
+    where T::Value: std::fmt::Debug
+{
+    source.into_field_types().map(|value| format!("{:?}", value)).collect()
+}
+
+assert_eq!(vec!["first", "second_field"], names::<Test>());
+assert_eq!(<Test as FieldNames>::COUNT, 2);
+
+let test = Test {
+    first: 1,
+    second_field: None,
+};
+assert_eq!(vec!["First(1)", "SecondField(None)"], dump(test));
+```
 
 ## Example
 
@@ -77,6 +222,13 @@ fn main() {
 }
 ```
 
+## Crate features
+
+ * `alloc` (enabled by default) enables the generated code that requires the `alloc` crate
+ * `serde` enables `serde` option of both derives
+ * `serde_json` enables `serde` and `alloc` features, `json_patch` option of `FieldName` derive
+   and [`merge_patch`] function
+
 ## Usage
 
 If you're using Cargo, just add it to your Cargo.toml:
@@ -87,299 +239,218 @@ field_types = "*"
 ```
 
 Use `FieldName` and/or `FieldType` in `derive` struct attribute.
-!*/
 
-extern crate proc_macro;
-extern crate syn;
-extern crate quote;
-extern crate heck;
-
-use std::iter::FromIterator;
-use proc_macro::TokenStream;
-use syn::{
-    DeriveInput, Ident, Type, Attribute, Fields, Meta, Path, PathArguments, PathSegment,
-    export::{Span, TokenStream2},
-    punctuated::Punctuated,
-};
-use quote::{quote, ToTokens};
-use heck::CamelCase;
-
-#[proc_macro_derive(FieldType, attributes(field_types, field_type, field_types_derive, field_type_derive))]
-pub fn field_type(input: TokenStream) -> TokenStream {
-    let ast: DeriveInput = syn::parse(input).unwrap();
-    let (vis, ty, generics) = (&ast.vis, &ast.ident, &ast.generics);
-    let enum_ty = Ident::new(&(ty.to_string() + "FieldType"), Span::call_site());
-    let derive = get_enum_derive(&ast.attrs, &["field_types_derive", "field_type_derive"], quote! {});
-
-    let fields = filter_fields(match ast.data {
-        syn::Data::Struct(ref s) => &s.fields,
-        _ => panic!("FieldType can only be derived for structures"),
-    }, "field_type");
-
-    if fields.is_empty() {
-        panic!("FieldType can only be derived for non-empty structures");
-    }
+The generated code refers only to fully qualified `::core` paths, so the derives can be used
+in `#![no_std]` crates and in modules with `#![no_implicit_prelude]`. This requires Rust 2018 edition or later.
+!*/
 
-    let field_type_variants = fields.iter()
-        .map(|(_, field_ty, variant_ident)| {
-            quote! {
-                #variant_ident(#field_ty)
-            }
-        });
-
-    let field_type_constructs = fields.iter()
-        .map(|(field_ident, _, variant_ident)| {
-            quote! {
-                #enum_ty::#variant_ident(#field_ident)
-            }
-        });
-
-    let from_field_type_constructs = field_type_constructs.clone();
-
-    let fields_idents = fields.iter()
-        .map(|(field_ident, _, _)| {
-            quote! {
-                #field_ident
-            }
-        });
-
-    let destructuring = quote! { #ty { #(#fields_idents,)* .. } };
-
-    let fields_count = fields.len();
-
-    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
-
-    let converter = if generics.params.is_empty() {
-        quote! {
-            impl From<#ty> for [#enum_ty; #fields_count] {
-                fn from(source: #ty) -> Self {
-                    let #destructuring = source;
-                    [#(#from_field_type_constructs),*]
-                }
-            }
-        }
-    } else {
-        quote! {
-            impl #impl_generics Into<[#enum_ty #ty_generics; #fields_count]> for #ty #ty_generics
-                #where_clause
-            {
-                fn into(self) -> [#enum_ty #ty_generics; #fields_count] {
-                    let #destructuring = self;
-                    [#(#from_field_type_constructs),*]
-                }
-            }
-        }
+#![no_std]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+pub use field_types_derive::{FieldName, FieldType};
+pub use any::TypeMismatch;
+#[cfg(feature = "alloc")]
+pub use history::History;
+#[cfg(feature = "serde_json")]
+pub use json::{merge_patch, MergePatchError};
+pub use map::FieldNameMap;
+pub use partial::MissingFields;
+pub use path::{FieldPath, FieldPathName, FieldPaths, Lens, Then};
+pub use set::{FieldNameSet, FieldNameSetIter};
+#[cfg(feature = "serde")]
+pub use serde_support::{DeserializeFieldType, SerializeFieldType};
+
+mod any;
+#[cfg(feature = "alloc")]
+mod history;
+#[cfg(feature = "serde_json")]
+mod json;
+mod map;
+mod names;
+mod partial;
+mod path;
+#[cfg(all(feature = "serde", feature = "alloc"))]
+pub mod serde_sparse;
+#[cfg(feature = "serde")]
+mod serde_support;
+mod set;
+
+/// Items used by the generated code, not public API.
+#[doc(hidden)]
+pub mod __private {
+    #[cfg(feature = "alloc")]
+    pub use alloc::boxed::Box;
+    #[cfg(feature = "serde")]
+    pub use serde;
+    #[cfg(feature = "serde_json")]
+    pub use serde_json;
+    #[cfg(feature = "serde_json")]
+    pub use alloc::vec::Vec;
+    #[cfg(feature = "serde_json")]
+    pub use crate::json::{merge_field, push_replace_op};
+    pub use crate::names::{concat_names, prefixed_bytes, prefixed_len, prefixed_names};
+    #[cfg(feature = "serde")]
+    pub use crate::serde_support::{
+        deserialize_field_name, deserialize_field_type, serialize_field_name, serialize_field_type,
     };
+}
 
-    let tokens = quote! {
-        #derive
-        #vis enum #enum_ty #generics
-            #where_clause
-        {
-            #(#field_type_variants),*
-        }
+use core::{iter::FusedIterator, marker::PhantomData, ops::Range};
 
-        #converter
+/// The `..FieldName` enum, which variants correspond to the fields of a struct.
+///
+/// This trait is implemented by `FieldName` derive.
+pub trait FieldName: Sized + 'static {
+    /// Number of the variants.
+    const COUNT: usize;
 
-        impl #impl_generics #ty #ty_generics
-            #where_clause
-        {
-            #vis fn into_field_type_array(self) -> [#enum_ty #ty_generics; #fields_count] {
-                let #destructuring = self;
-                [#(#field_type_constructs),*]
-            }
-        }
-    };
-    tokens.into()
-}
+    /// The names of the fields of all variants, in the order of their positions.
+    const NAMES: &'static [&'static str];
+
+    /// Returns the name of the corresponding field.
+    fn name(&self) -> &'static str;
+
+    /// Returns the variant for the field with the given name.
+    fn by_name(name: &str) -> Option<Self>;
+
+    /// Returns the position of the corresponding field, in `0..COUNT`.
+    fn index(&self) -> usize;
 
-#[proc_macro_derive(FieldName, attributes(field_types, field_name, field_types_derive, field_name_derive))]
-pub fn field_name(input: TokenStream) -> TokenStream {
-    let ast: DeriveInput = syn::parse(input).unwrap();
-    let (vis, ty, generics) = (&ast.vis, &ast.ident, &ast.generics);
-    let enum_ty = Ident::new(&(ty.to_string() + "FieldName"), Span::call_site());
-    let derive = get_enum_derive(&ast.attrs, &["field_types_derive", "field_name_derive"],
-                            quote! { #[derive(Debug, PartialEq, Eq, Clone, Copy)] });
-
-    let fields = filter_fields(match ast.data {
-        syn::Data::Struct(ref s) => &s.fields,
-        _ => panic!("FieldName can only be derived for structures"),
-    }, "field_name");
-
-    if fields.is_empty() {
-        panic!("FieldName can only be derived for non-empty structures");
+    /// Returns the variant for the field at the given position.
+    fn from_index(index: usize) -> Option<Self>;
+
+    /// Returns the doc comment of the corresponding field, if any.
+    fn description(&self) -> Option<&'static str> {
+        None
     }
 
-    let field_name_variants = fields.iter()
-        .map(|(_, _, variant_ident)| {
-            quote! {
-                #variant_ident
-            }
-        });
-
-    let field_name_to_strs = fields.iter()
-        .map(|(field_ident, _, variant_ident)| {
-            let field_name = field_ident.to_string();
-            quote! {
-                #enum_ty::#variant_ident => #field_name
-            }
-        });
-
-    let field_name_by_strs = fields.iter()
-        .map(|(_, _, variant_ident)| {
-            quote! {
-                if #enum_ty::#variant_ident.name() == name { return Some(#enum_ty::#variant_ident) }
-            }
-        });
-
-    let field_name_constructs = fields.iter()
-        .map(|(_, _, variant_ident)| {
-            quote! {
-                #enum_ty::#variant_ident
-            }
-        });
-
-    let from_field_name_constructs = field_name_constructs.clone();
-
-    let fields_count = fields.len();
-
-    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
-    let from_lifetime = quote! { 'field_name_from_lifetime__ };
-
-    let mut impl_generics_tokens = TokenStream2::new();
-    impl_generics.to_tokens(&mut impl_generics_tokens);
-    if impl_generics_tokens.is_empty() {
-        impl_generics_tokens = quote! { <#from_lifetime> };
-    } else {
-        let mut tokens: Vec<_> = quote! { #from_lifetime, }.into_iter().collect();
-        let mut gen_iter = impl_generics_tokens.into_iter();
-        if let Some(token) = gen_iter.next() {
-            tokens.insert(0, token);
-        }
-        tokens.extend(gen_iter);
-        impl_generics_tokens = TokenStream2::from_iter(tokens);
+    /// Returns the type of the corresponding field as it is written in the struct.
+    fn type_name(&self) -> &'static str;
+
+    /// Returns the `TypeId` of the corresponding field type, if it doesn't depend on the generic parameters of the struct.
+    fn type_id(&self) -> Option<core::any::TypeId> {
+        None
     }
+}
 
-    let tokens = quote! {
-        #derive
-        #vis enum #enum_ty {
-            #(#field_name_variants),*
-        }
+/// A struct, which fields are enumerated by a `..FieldName` enum.
+///
+/// This trait is implemented by `FieldName` derive.
+pub trait FieldNames {
+    /// The `..FieldName` enum of the struct.
+    type Name: FieldName;
 
-        impl #enum_ty {
-            #vis fn name(&self) -> &'static str {
-                match *self {
-                    #(#field_name_to_strs),*
-                }
-            }
-
-            #vis fn by_name(name: &str) -> Option<Self> {
-                #(#field_name_by_strs)*
-                None
-            }
-        }
+    /// Number of the (non-skipped) fields.
+    const COUNT: usize = <Self::Name as FieldName>::COUNT;
 
-        impl #impl_generics #ty #ty_generics
-            #where_clause
-        {
-            #vis fn as_field_name_array() -> [#enum_ty; #fields_count] {
-                [#(#field_name_constructs),*]
-            }
-        }
+    /// Returns an iterator over the variants for all fields, in the order of declaration.
+    fn field_names() -> FieldNamesIter<Self::Name> {
+        FieldNamesIter::new()
+    }
+}
+
+/// A struct, which can be converted into the values of a `..FieldType` enum.
+///
+/// This trait is implemented by `FieldType` derive.
+pub trait FieldTypes {
+    /// The `..FieldType` enum of the struct.
+    type Value;
+
+    /// The iterator over the field values.
+    type IntoFieldTypes: Iterator<Item = Self::Value> + ExactSizeIterator + DoubleEndedIterator;
+
+    /// Number of the (non-skipped) fields.
+    const COUNT: usize;
+
+    /// The names of the fields of all variants of the `..FieldType` enum, in the order of declaration.
+    const NAMES: &'static [&'static str];
 
-        impl #impl_generics_tokens From<& #from_lifetime #ty #ty_generics> for [#enum_ty; #fields_count] {
-            fn from(_source: & #from_lifetime #ty #ty_generics) -> Self {
-                [#(#from_field_name_constructs),*]
-            }
+    /// Converts the struct into an iterator over the values of all fields, in the order of declaration.
+    fn into_field_types(self) -> Self::IntoFieldTypes;
+}
+
+/// A struct, which fields can be set with the values of a `..FieldType` enum.
+///
+/// This trait is implemented by `FieldType` derive with `history` option.
+pub trait SetField: FieldTypes {
+    /// Sets the field corresponding to the variant of the value, and returns the previous value of the field.
+    fn set_field(&mut self, value: Self::Value) -> Self::Value;
+}
+
+/// A field of a struct, statically typed by a marker type.
+///
+/// This trait is implemented by `FieldName` derive with `field_keys` option
+/// for the marker types in `..._fields` module, e.g. `test_fields::SecondField`.
+pub trait Field {
+    /// The struct, which has the field.
+    type Owner: ?Sized;
+
+    /// The type of the field.
+    type Type: ?Sized;
+
+    /// The name of the field.
+    const NAME: &'static str;
+
+    /// Returns a reference to the field of the struct.
+    fn get(owner: &Self::Owner) -> &Self::Type;
+
+    /// Returns a mutable reference to the field of the struct.
+    fn get_mut(owner: &mut Self::Owner) -> &mut Self::Type;
+}
+
+/// An iterator over all variants of a `..FieldName` enum.
+///
+/// This struct is created by [`FieldNames::field_names`] method.
+pub struct FieldNamesIter<N> {
+    range: Range<usize>,
+    _name: PhantomData<fn() -> N>,
+}
+
+impl<N: FieldName> FieldNamesIter<N> {
+    /// Returns an iterator over all variants of the `..FieldName` enum, in the order of declaration.
+    pub fn new() -> Self {
+        Self {
+            range: 0..N::COUNT,
+            _name: PhantomData,
         }
-    };
-    tokens.into()
+    }
 }
 
-fn get_enum_derive(attrs: &[Attribute], derive_attr_names: &[&str], default: TokenStream2) -> TokenStream2 {
-    attrs.iter()
-        .filter_map(|attr| attr.parse_meta()
-            .ok()
-            .and_then(|meta| {
-                for attr_name in derive_attr_names {
-                    let attr_ident = Some(Ident::new(attr_name, Span::call_site()));
-                    let ident = meta.path().get_ident();
-                    if ident == attr_ident.as_ref() {
-                        if let Meta::List(meta_list) = &meta {
-                            let mut meta_list = meta_list.clone();
-                            meta_list.path = Path {
-                                leading_colon: None,
-                                segments: {
-                                    let mut segments = Punctuated::new();
-                                    segments.push(PathSegment {
-                                        ident: Ident::new("derive", Span::call_site()),
-                                        arguments: PathArguments::None,
-                                    });
-                                    segments
-                                }
-                            };
-                            return Some(meta_list);
-                        }
-                    }
-                }
-                None
-            })
-        )
-        .next()
-        .map(|meta_list| quote! { #[#meta_list] })
-        .unwrap_or(default)
+impl<N: FieldName> Default for FieldNamesIter<N> {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
-fn filter_fields(fields: &Fields, skip_attr_name: &str) -> Vec<(Ident, Type, Ident)> {
-    fields.iter()
-        .filter_map(|field| {
-            if field.attrs.iter()
-                .find(|attr| has_skip_attr(attr, &["field_types", skip_attr_name]))
-                .is_none() && field.ident.is_some()
-            {
-                let field_ty = field.ty.clone();
-                let field_ident = field.ident.as_ref().unwrap().clone();
-                let field_name = field.ident.as_ref().unwrap().to_string();
-                let variant_ident = Ident::new(&field_name.to_camel_case(), Span::call_site());
-                Some((field_ident, field_ty, variant_ident))
-            } else {
-                None
-            }
-        })
-        .collect::<Vec<_>>()
+impl<N> Clone for FieldNamesIter<N> {
+    fn clone(&self) -> Self {
+        Self {
+            range: self.range.clone(),
+            _name: PhantomData,
+        }
+    }
 }
 
-fn has_skip_attr(attr: &Attribute, attr_names: &[&str]) -> bool {
-    attr.parse_meta()
-        .ok()
-        .and_then(|meta| {
-            for attr_name in attr_names {
-                let attr_ident = Some(Ident::new(attr_name, Span::call_site()));
-                let ident = meta.path().get_ident();
-                if ident == attr_ident.as_ref() {
-                    return Some(meta);
-                }
-            }
-            None
-        })
-        .map(|meta| {
-            let value = match meta {
-                Meta::List(ref list) => list.nested.first()
-                    .expect("Attribute value can't be empty")
-                    .clone()
-                    .into_token_stream()
-                    .to_string(),
-
-                Meta::NameValue(ref name_value) => name_value.lit
-                    .clone()
-                    .into_token_stream()
-                    .to_string(),
-
-                _ => panic!("Unknown attribute value, only `skip` allowed."),
-            };
-            if value != "skip" && value.find("\"skip\"").is_none() {
-                panic!("Unknown attribute value `{}`, only `skip` allowed.", value);
-            }
-        })
-        .is_some()
+impl<N: FieldName> Iterator for FieldNamesIter<N> {
+    type Item = N;
+
+    fn next(&mut self) -> Option<N> {
+        self.range.next().and_then(N::from_index)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+impl<N: FieldName> DoubleEndedIterator for FieldNamesIter<N> {
+    fn next_back(&mut self) -> Option<N> {
+        self.range.next_back().and_then(N::from_index)
+    }
 }
+
+impl<N: FieldName> ExactSizeIterator for FieldNamesIter<N> {}
+
+impl<N: FieldName> FusedIterator for FieldNamesIter<N> {}
@@ -0,0 +1,178 @@
+use core::{
+    array, fmt,
+    hash::{Hash, Hasher},
+    iter::{Enumerate, Map},
+    marker::PhantomData,
+    ops::{Index, IndexMut},
+    slice,
+};
+
+use crate::FieldName;
+
+/// A map from `..FieldName` variants to values, stored as an array with a value for every variant.
+///
+/// The `FieldName` derive generates the `..FieldNameMap<V>` alias for this type,
+/// with `LEN` equal to the number of the enum variants.
+pub struct FieldNameMap<N, V, const LEN: usize> {
+    values: [V; LEN],
+    _name: PhantomData<fn() -> N>,
+}
+
+impl<N: FieldName, V, const LEN: usize> FieldNameMap<N, V, LEN> {
+    const LEN_CHECK: () = assert!(LEN == N::COUNT, "FieldNameMap length must be equal to the number of variants");
+
+    fn name(index: usize) -> N {
+        N::from_index(index).expect("FieldNameMap index must correspond to a variant")
+    }
+
+    /// Creates the map with the values returned by `f` for every variant.
+    pub fn from_fn(mut f: impl FnMut(N) -> V) -> Self {
+        Self::from_array(array::from_fn(|index| f(Self::name(index))))
+    }
+
+    /// Creates the map with the values in the order of the fields declaration.
+    pub fn from_array(values: [V; LEN]) -> Self {
+        let () = Self::LEN_CHECK;
+        Self {
+            values,
+            _name: PhantomData,
+        }
+    }
+
+    /// Returns the values in the order of the fields declaration.
+    pub fn into_array(self) -> [V; LEN] {
+        self.values
+    }
+
+    /// Returns the values in the order of the fields declaration.
+    pub fn as_array(&self) -> &[V; LEN] {
+        &self.values
+    }
+
+    /// Returns the mutable values in the order of the fields declaration.
+    pub fn as_mut_array(&mut self) -> &mut [V; LEN] {
+        &mut self.values
+    }
+
+    /// Returns the number of the values, which is equal to the number of variants.
+    pub const fn len(&self) -> usize {
+        LEN
+    }
+
+    /// Returns `true` if the enum has no variants.
+    pub const fn is_empty(&self) -> bool {
+        LEN == 0
+    }
+
+    /// Returns the value for the variant.
+    pub fn get(&self, name: &N) -> &V {
+        &self.values[name.index()]
+    }
+
+    /// Returns the mutable value for the variant.
+    pub fn get_mut(&mut self, name: &N) -> &mut V {
+        &mut self.values[name.index()]
+    }
+
+    /// Replaces the value for the variant, returning the previous value.
+    pub fn insert(&mut self, name: &N, value: V) -> V {
+        core::mem::replace(self.get_mut(name), value)
+    }
+
+    /// Returns an iterator over the variants and their values.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (N, &V)> + DoubleEndedIterator {
+        self.values.iter()
+            .enumerate()
+            .map(|(index, value)| (Self::name(index), value))
+    }
+
+    /// Returns an iterator over the variants and their mutable values.
+    pub fn iter_mut(&mut self) -> impl ExactSizeIterator<Item = (N, &mut V)> + DoubleEndedIterator {
+        self.values.iter_mut()
+            .enumerate()
+            .map(|(index, value)| (Self::name(index), value))
+    }
+
+    /// Returns an iterator over the values.
+    pub fn values(&self) -> slice::Iter<'_, V> {
+        self.values.iter()
+    }
+
+    /// Returns an iterator over the mutable values.
+    pub fn values_mut(&mut self) -> slice::IterMut<'_, V> {
+        self.values.iter_mut()
+    }
+
+    /// Creates the map with the values returned by `f` for every variant and its value.
+    pub fn map<U>(self, mut f: impl FnMut(N, V) -> U) -> FieldNameMap<N, U, LEN> {
+        let mut values = self.into_iter();
+        FieldNameMap::from_fn(|_| {
+            let (name, value) = values.next().expect("FieldNameMap must have a value for every variant");
+            f(name, value)
+        })
+    }
+}
+
+impl<N: FieldName, V: Default, const LEN: usize> Default for FieldNameMap<N, V, LEN> {
+    fn default() -> Self {
+        Self::from_fn(|_| V::default())
+    }
+}
+
+impl<N, V: Clone, const LEN: usize> Clone for FieldNameMap<N, V, LEN> {
+    fn clone(&self) -> Self {
+        Self {
+            values: self.values.clone(),
+            _name: PhantomData,
+        }
+    }
+}
+
+impl<N, V: Copy, const LEN: usize> Copy for FieldNameMap<N, V, LEN> {}
+
+impl<N, V: PartialEq, const LEN: usize> PartialEq for FieldNameMap<N, V, LEN> {
+    fn eq(&self, other: &Self) -> bool {
+        self.values == other.values
+    }
+}
+
+impl<N, V: Eq, const LEN: usize> Eq for FieldNameMap<N, V, LEN> {}
+
+impl<N, V: Hash, const LEN: usize> Hash for FieldNameMap<N, V, LEN> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.values.hash(state);
+    }
+}
+
+impl<N: FieldName, V: fmt::Debug, const LEN: usize> fmt::Debug for FieldNameMap<N, V, LEN> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(self.iter().map(|(name, value)| (name.name(), value)))
+            .finish()
+    }
+}
+
+impl<N: FieldName, V, const LEN: usize> Index<N> for FieldNameMap<N, V, LEN> {
+    type Output = V;
+
+    fn index(&self, name: N) -> &V {
+        self.get(&name)
+    }
+}
+
+impl<N: FieldName, V, const LEN: usize> IndexMut<N> for FieldNameMap<N, V, LEN> {
+    fn index_mut(&mut self, name: N) -> &mut V {
+        self.get_mut(&name)
+    }
+}
+
+impl<N: FieldName, V, const LEN: usize> IntoIterator for FieldNameMap<N, V, LEN> {
+    type Item = (N, V);
+    type IntoIter = Map<Enumerate<array::IntoIter<V, LEN>>, fn((usize, V)) -> (N, V)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIterator::into_iter(self.values)
+            .enumerate()
+            .map(|(index, value)| (Self::name(index), value))
+    }
+}
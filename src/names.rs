@@ -0,0 +1,96 @@
+/// Returns the total length of the names with the prefix.
+pub const fn prefixed_len(prefix: &str, names: &[&str]) -> usize {
+    let mut len = 0;
+    let mut index = 0;
+    while index < names.len() {
+        len += prefix.len() + names[index].len();
+        index += 1;
+    }
+    len
+}
+
+/// Concatenates the names with the prefix into the bytes of `prefixed_len(prefix, names)` length.
+pub const fn prefixed_bytes<const LEN: usize>(prefix: &str, names: &[&str]) -> [u8; LEN] {
+    let mut bytes = [0; LEN];
+    let mut pos = 0;
+    let mut index = 0;
+    while index < names.len() {
+        pos = copy_bytes(&mut bytes, pos, prefix.as_bytes());
+        pos = copy_bytes(&mut bytes, pos, names[index].as_bytes());
+        index += 1;
+    }
+    bytes
+}
+
+const fn copy_bytes<const LEN: usize>(bytes: &mut [u8; LEN], mut pos: usize, source: &[u8]) -> usize {
+    let mut index = 0;
+    while index < source.len() {
+        bytes[pos] = source[index];
+        pos += 1;
+        index += 1;
+    }
+    pos
+}
+
+/// Splits the bytes returned by `prefixed_bytes(prefix, names)` into the prefixed names.
+pub const fn prefixed_names<const COUNT: usize>(
+    bytes: &'static [u8],
+    prefix: &str,
+    names: &[&str],
+) -> [&'static str; COUNT] {
+    let mut prefixed = [""; COUNT];
+    let mut rest = bytes;
+    let mut index = 0;
+    while index < COUNT {
+        let (name, tail) = rest.split_at(prefix.len() + names[index].len());
+        prefixed[index] = match core::str::from_utf8(name) {
+            Ok(name) => name,
+            Err(_) => panic!("prefixed field name must be a valid UTF-8 string"),
+        };
+        rest = tail;
+        index += 1;
+    }
+    prefixed
+}
+
+/// Concatenates the lists of the names into an array of `COUNT` names, which must be unique.
+pub const fn concat_names<const COUNT: usize>(lists: &[&[&'static str]]) -> [&'static str; COUNT] {
+    let mut names = [""; COUNT];
+    let mut pos = 0;
+    let mut list = 0;
+    while list < lists.len() {
+        let mut index = 0;
+        while index < lists[list].len() {
+            names[pos] = lists[list][index];
+            pos += 1;
+            index += 1;
+        }
+        list += 1;
+    }
+    assert!(pos == COUNT, "the number of the names must be equal to the number of the variants");
+    let mut index = 0;
+    while index < COUNT {
+        let mut other = index + 1;
+        while other < COUNT {
+            assert!(!str_eq(names[index], names[other]), "the field names of the flattened fields must be unique");
+            other += 1;
+        }
+        index += 1;
+    }
+    names
+}
+
+const fn str_eq(first: &str, second: &str) -> bool {
+    let (first, second) = (first.as_bytes(), second.as_bytes());
+    if first.len() != second.len() {
+        return false;
+    }
+    let mut index = 0;
+    while index < first.len() {
+        if first[index] != second[index] {
+            return false;
+        }
+        index += 1;
+    }
+    true
+}
@@ -0,0 +1,46 @@
+use core::fmt;
+
+use crate::{FieldName, FieldNameSet};
+
+/// The error of converting a partial struct into the struct, which lists the fields that are not set.
+///
+/// This error is returned by `TryFrom` implementation, generated with `partial` option.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct MissingFields<N> {
+    fields: FieldNameSet<N>,
+}
+
+impl<N: FieldName> MissingFields<N> {
+    #[doc(hidden)]
+    pub fn new(fields: FieldNameSet<N>) -> Self {
+        Self { fields }
+    }
+
+    /// Returns the fields that are not set.
+    pub fn fields(&self) -> FieldNameSet<N> {
+        self.fields
+    }
+}
+
+impl<N: FieldName> fmt::Debug for MissingFields<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MissingFields")
+            .field("fields", &self.fields)
+            .finish()
+    }
+}
+
+impl<N: FieldName> fmt::Display for MissingFields<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("missing fields: ")?;
+        for (index, name) in self.fields.iter().enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "`{}`", name.name())?;
+        }
+        Ok(())
+    }
+}
+
+impl<N: FieldName> core::error::Error for MissingFields<N> {}
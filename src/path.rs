@@ -0,0 +1,197 @@
+use core::{
+    fmt::{self, Write},
+    marker::PhantomData,
+};
+
+use crate::Field;
+
+/// A path to a (possibly nested) field of a struct, e.g. `address.city`.
+///
+/// This trait is implemented by `FieldName` derive for the `..FieldName` enum
+/// and for the `..FieldPath` enum, generated if the struct has `nested` fields.
+pub trait FieldPath: Sized + 'static {
+    /// Writes the dot-separated names of the fields of the path.
+    fn write_path<W: Write + ?Sized>(&self, out: &mut W) -> fmt::Result;
+
+    /// Returns the path for the dot-separated names of the fields.
+    fn by_path(path: &str) -> Option<Self>;
+
+    /// Returns the displayable dot-separated names of the fields of the path.
+    fn path(&self) -> FieldPathName<'_, Self> {
+        FieldPathName(self)
+    }
+}
+
+/// A struct, which (possibly nested) fields are addressed by a [`FieldPath`].
+///
+/// This trait is implemented by `FieldName` derive.
+pub trait FieldPaths {
+    /// The `..FieldPath` enum of the struct, or the `..FieldName` enum if the struct has no `nested` fields.
+    type Path: FieldPath;
+}
+
+/// The dot-separated names of the fields of a [`FieldPath`].
+///
+/// It can be displayed or compared with a string without allocation.
+pub struct FieldPathName<'a, P>(pub &'a P);
+
+impl<P> Clone for FieldPathName<'_, P> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<P> Copy for FieldPathName<'_, P> {}
+
+impl<P: FieldPath> fmt::Display for FieldPathName<'_, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.write_path(f)
+    }
+}
+
+impl<P: FieldPath> fmt::Debug for FieldPathName<'_, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_char('"')?;
+        self.0.write_path(f)?;
+        f.write_char('"')
+    }
+}
+
+impl<P: FieldPath> PartialEq<str> for FieldPathName<'_, P> {
+    fn eq(&self, other: &str) -> bool {
+        struct Matcher<'s>(&'s str);
+
+        impl Write for Matcher<'_> {
+            fn write_str(&mut self, s: &str) -> fmt::Result {
+                self.0 = self.0.strip_prefix(s).ok_or(fmt::Error)?;
+                Ok(())
+            }
+        }
+
+        let mut matcher = Matcher(other);
+        self.0.write_path(&mut matcher).is_ok() && matcher.0.is_empty()
+    }
+}
+
+impl<P: FieldPath> PartialEq<&str> for FieldPathName<'_, P> {
+    fn eq(&self, other: &&str) -> bool {
+        *self == **other
+    }
+}
+
+impl<P: FieldPath> PartialEq<FieldPathName<'_, P>> for str {
+    fn eq(&self, other: &FieldPathName<'_, P>) -> bool {
+        *other == *self
+    }
+}
+
+impl<P: FieldPath> PartialEq<FieldPathName<'_, P>> for &str {
+    fn eq(&self, other: &FieldPathName<'_, P>) -> bool {
+        *other == **self
+    }
+}
+
+/// A statically typed accessor of a (possibly nested) field of a struct.
+///
+/// This trait is implemented for all [`Field`] marker types, and the lenses
+/// can be composed with [`Lens::then`] to access the fields of nested structs.
+pub trait Lens {
+    /// The struct, which has the field.
+    type Owner: ?Sized;
+
+    /// The type of the field.
+    type Type: ?Sized;
+
+    /// Returns a reference to the field of the struct.
+    fn get<'o>(owner: &'o Self::Owner) -> &'o Self::Type
+        where Self: 'o;
+
+    /// Returns a mutable reference to the field of the struct.
+    fn get_mut<'o>(owner: &'o mut Self::Owner) -> &'o mut Self::Type
+        where Self: 'o;
+
+    /// Writes the dot-separated names of the fields, accessed by the lens.
+    fn write_path<W: Write + ?Sized>(out: &mut W) -> fmt::Result;
+
+    /// Replaces the field of the struct, returning the previous value.
+    fn set(owner: &mut Self::Owner, value: Self::Type) -> Self::Type
+        where Self::Type: Sized
+    {
+        core::mem::replace(Self::get_mut(owner), value)
+    }
+
+    /// Returns the lens, which accesses the field of the `next` lens in the field of `self` lens.
+    fn then<B: Lens<Owner = Self::Type>>(self, next: B) -> Then<Self, B>
+        where Self: Sized
+    {
+        let _ = next;
+        Then::new()
+    }
+}
+
+impl<F: Field> Lens for F {
+    type Owner = F::Owner;
+    type Type = F::Type;
+
+    fn get<'o>(owner: &'o Self::Owner) -> &'o Self::Type
+        where Self: 'o
+    {
+        F::get(owner)
+    }
+
+    fn get_mut<'o>(owner: &'o mut Self::Owner) -> &'o mut Self::Type
+        where Self: 'o
+    {
+        F::get_mut(owner)
+    }
+
+    fn write_path<W: Write + ?Sized>(out: &mut W) -> fmt::Result {
+        out.write_str(F::NAME)
+    }
+}
+
+/// The composition of two lenses, created by [`Lens::then`].
+pub struct Then<A, B>(PhantomData<fn() -> (A, B)>);
+
+impl<A, B> Then<A, B> {
+    pub const fn new() -> Self {
+        Then(PhantomData)
+    }
+}
+
+impl<A, B> Default for Then<A, B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A, B> Clone for Then<A, B> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<A, B> Copy for Then<A, B> {}
+
+impl<A: Lens, B: Lens<Owner = A::Type>> Lens for Then<A, B> {
+    type Owner = A::Owner;
+    type Type = B::Type;
+
+    fn get<'o>(owner: &'o Self::Owner) -> &'o Self::Type
+        where Self: 'o
+    {
+        B::get(A::get(owner))
+    }
+
+    fn get_mut<'o>(owner: &'o mut Self::Owner) -> &'o mut Self::Type
+        where Self: 'o
+    {
+        B::get_mut(A::get_mut(owner))
+    }
+
+    fn write_path<W: Write + ?Sized>(out: &mut W) -> fmt::Result {
+        A::write_path(out)?;
+        out.write_char('.')?;
+        B::write_path(out)
+    }
+}
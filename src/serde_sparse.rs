@@ -0,0 +1,55 @@
+//! (De)serialization of `Vec` of `..FieldType` values as a sparse object `{ "field": value, .. }`.
+//!
+//! Use it with `#[serde(with = "field_types::serde_sparse")]` attribute of a `Vec<TestFieldType>` field,
+//! where `TestFieldType` is derived with `serde` option.
+
+use alloc::{vec, vec::Vec};
+use core::{fmt, marker::PhantomData};
+
+use serde::{
+    de::{self, Deserializer, MapAccess, Visitor},
+    ser::{SerializeMap, Serializer},
+};
+
+use crate::serde_support::{DeserializeFieldType, FieldKey, SerializeFieldType};
+
+/// Serializes the values as the entries of a map.
+pub fn serialize<T: SerializeFieldType, S: Serializer>(values: &[T], serializer: S) -> Result<S::Ok, S::Error> {
+    let mut map = serializer.serialize_map(Some(values.len()))?;
+    for value in values {
+        value.serialize_entry(&mut map)?;
+    }
+    map.end()
+}
+
+/// Deserializes the values from the entries of a map, in the order of the entries.
+/// A field can't occur more than once.
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Vec<T>, D::Error>
+    where T: DeserializeFieldType<'de>, D: Deserializer<'de>
+{
+    deserializer.deserialize_map(SparseVisitor(PhantomData))
+}
+
+struct SparseVisitor<T>(PhantomData<fn() -> T>);
+
+impl<'de, T: DeserializeFieldType<'de>> Visitor<'de> for SparseVisitor<T> {
+    type Value = Vec<T>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a map of the fields")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Vec<T>, A::Error> {
+        // The size hint comes from the input, but a valid map has no more entries than the variants
+        let count = T::variant_count();
+        let mut values = Vec::with_capacity(map.size_hint().unwrap_or(0).min(count));
+        let mut seen = vec![false; count];
+        while let Some(index) = map.next_key_seed(FieldKey::<T>(PhantomData))? {
+            if core::mem::replace(&mut seen[index], true) {
+                return Err(de::Error::duplicate_field(T::variant_name(index).unwrap_or_default()));
+            }
+            values.push(T::deserialize_variant(index, &mut map)?);
+        }
+        Ok(values)
+    }
+}
@@ -0,0 +1,169 @@
+use core::{fmt, marker::PhantomData};
+
+use serde::{
+    de::{self, DeserializeSeed, Deserializer, IgnoredAny, MapAccess, Visitor},
+    ser::SerializeMap,
+    Serializer,
+};
+
+use crate::{FieldName, FieldNamesIter};
+
+/// Serializes the variant as the name of the corresponding field.
+pub fn serialize_field_name<N: FieldName, S: Serializer>(name: &N, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(name.name())
+}
+
+/// Deserializes the variant from the name of the corresponding field.
+pub fn deserialize_field_name<'de, N: FieldName, D: Deserializer<'de>>(deserializer: D) -> Result<N, D::Error> {
+    deserializer.deserialize_str(FieldNameVisitor(PhantomData))
+}
+
+struct FieldNameVisitor<N>(PhantomData<fn() -> N>);
+
+impl<'de, N: FieldName> Visitor<'de> for FieldNameVisitor<N> {
+    type Value = N;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "one of the field names: {}", FieldNames::<N>(PhantomData))
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<N, E> {
+        N::by_name(value).ok_or_else(|| {
+            E::custom(format_args!("unknown field name `{}`, expected one of: {}", value, FieldNames::<N>(PhantomData)))
+        })
+    }
+
+    fn visit_bytes<E: de::Error>(self, value: &[u8]) -> Result<N, E> {
+        match core::str::from_utf8(value) {
+            Ok(value) => self.visit_str(value),
+            Err(_) => Err(E::invalid_value(de::Unexpected::Bytes(value), &self)),
+        }
+    }
+}
+
+/// The comma-separated quoted names of all variants.
+pub(crate) struct FieldNames<N>(pub(crate) PhantomData<fn() -> N>);
+
+impl<N: FieldName> fmt::Display for FieldNames<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, name) in FieldNamesIter::<N>::new().enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "`{}`", name.name())?;
+        }
+        Ok(())
+    }
+}
+
+/// The `..FieldType` enum, which variants are serialized as single-entry maps `{ "field": value }`.
+///
+/// This trait is implemented by `FieldType` derive with `serde` option.
+pub trait SerializeFieldType {
+    /// Returns the position of the variant, including the variants of the flattened fields.
+    fn index(&self) -> usize;
+
+    /// Returns the field name of the variant.
+    fn name(&self) -> &'static str;
+
+    /// Serializes the value of the variant as the value of the map entry.
+    fn serialize_value<M: SerializeMap>(&self, map: &mut M) -> Result<(), M::Error>;
+
+    /// Serializes the variant as an entry of the map.
+    fn serialize_entry<M: SerializeMap>(&self, map: &mut M) -> Result<(), M::Error> {
+        map.serialize_key(self.name())?;
+        self.serialize_value(map)
+    }
+}
+
+/// The `..FieldType` enum, which variants are deserialized from single-entry maps `{ "field": value }`.
+///
+/// This trait is implemented by `FieldType` derive with `serde` option.
+pub trait DeserializeFieldType<'de>: Sized {
+    /// Returns the number of the variants, including the variants of the flattened fields.
+    fn variant_count() -> usize;
+
+    /// Returns the position of the variant for the field name.
+    fn variant_index(name: &str) -> Option<usize>;
+
+    /// Returns the field name of the variant at the position.
+    fn variant_name(index: usize) -> Option<&'static str>;
+
+    /// Deserializes the variant at the position from the value of the map entry.
+    fn deserialize_variant<A: MapAccess<'de>>(index: usize, map: &mut A) -> Result<Self, A::Error>;
+}
+
+/// Serializes the variant as a single-entry map.
+pub fn serialize_field_type<T: SerializeFieldType, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+    let mut map = serializer.serialize_map(Some(1))?;
+    value.serialize_entry(&mut map)?;
+    map.end()
+}
+
+/// Deserializes the variant from a single-entry map.
+pub fn deserialize_field_type<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where T: DeserializeFieldType<'de>, D: Deserializer<'de>
+{
+    deserializer.deserialize_map(FieldTypeVisitor(PhantomData))
+}
+
+struct FieldTypeVisitor<T>(PhantomData<fn() -> T>);
+
+impl<'de, T: DeserializeFieldType<'de>> Visitor<'de> for FieldTypeVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a map with a single field")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<T, A::Error> {
+        let index = map.next_key_seed(FieldKey::<T>(PhantomData))?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let value = T::deserialize_variant(index, &mut map)?;
+        if map.next_key::<IgnoredAny>()?.is_some() {
+            return Err(de::Error::invalid_length(2, &self));
+        }
+        Ok(value)
+    }
+}
+
+/// Deserializes the field name as the position of the variant.
+pub(crate) struct FieldKey<T>(pub(crate) PhantomData<fn() -> T>);
+
+impl<'de, T: DeserializeFieldType<'de>> DeserializeSeed<'de> for FieldKey<T> {
+    type Value = usize;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<usize, D::Error> {
+        deserializer.deserialize_str(self)
+    }
+}
+
+impl<'de, T: DeserializeFieldType<'de>> Visitor<'de> for FieldKey<T> {
+    type Value = usize;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "one of the field names: {}", VariantNames::<T>(PhantomData))
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<usize, E> {
+        T::variant_index(value).ok_or_else(|| {
+            E::custom(format_args!("unknown field name `{}`, expected one of: {}", value, VariantNames::<T>(PhantomData)))
+        })
+    }
+}
+
+/// The comma-separated quoted field names of all variants.
+struct VariantNames<T>(PhantomData<fn() -> T>);
+
+impl<'de, T: DeserializeFieldType<'de>> fmt::Display for VariantNames<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names = (0..T::variant_count()).filter_map(T::variant_name);
+        for (index, name) in names.enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "`{}`", name)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,308 @@
+use core::{
+    fmt,
+    hash::{Hash, Hasher},
+    iter::{FromIterator, FusedIterator},
+    marker::PhantomData,
+    ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, Sub, SubAssign},
+};
+
+use crate::FieldName;
+
+/// A set of `..FieldName` variants, stored as a bitset.
+///
+/// The set can contain no more than 128 variants, so using it with a larger enum fails to compile.
+/// The `FieldName` derive generates the `..FieldNameSet` alias for this type,
+/// if the struct has no more than 128 fields or has `flatten` fields.
+pub struct FieldNameSet<N> {
+    bits: u128,
+    _name: PhantomData<fn() -> N>,
+}
+
+impl<N: FieldName> FieldNameSet<N> {
+    /// The set without any variants.
+    pub const EMPTY: Self = Self::from_bits(0);
+
+    /// The set with all variants.
+    pub const ALL: Self = Self::from_bits(if N::COUNT >= 128 { u128::MAX } else { (1 << N::COUNT) - 1 });
+
+    /// Fails the compilation, if the enum has more variants than the bitset can contain.
+    const CAPACITY_CHECK: () = assert!(N::COUNT <= 128, "FieldNameSet can't contain more than 128 variants");
+
+    const fn from_bits(bits: u128) -> Self {
+        let () = Self::CAPACITY_CHECK;
+        Self {
+            bits,
+            _name: PhantomData,
+        }
+    }
+
+    fn bit(name: &N) -> u128 {
+        let () = Self::CAPACITY_CHECK;
+        1 << name.index()
+    }
+
+    /// Returns the set without any variants.
+    pub const fn empty() -> Self {
+        Self::EMPTY
+    }
+
+    /// Returns the set with all variants.
+    pub const fn all() -> Self {
+        Self::ALL
+    }
+
+    /// Returns the number of variants in the set.
+    pub const fn len(&self) -> usize {
+        self.bits.count_ones() as usize
+    }
+
+    /// Returns `true` if the set contains no variants.
+    pub const fn is_empty(&self) -> bool {
+        self.bits == 0
+    }
+
+    /// Returns `true` if the set contains all variants.
+    pub const fn is_all(&self) -> bool {
+        self.bits == Self::ALL.bits
+    }
+
+    /// Returns `true` if the set contains the variant.
+    pub fn contains(&self, name: &N) -> bool {
+        self.bits & Self::bit(name) != 0
+    }
+
+    /// Adds the variant to the set. Returns `true` if the set did not contain it.
+    pub fn insert(&mut self, name: N) -> bool {
+        let bit = Self::bit(&name);
+        let inserted = self.bits & bit == 0;
+        self.bits |= bit;
+        inserted
+    }
+
+    /// Removes the variant from the set. Returns `true` if the set contained it.
+    pub fn remove(&mut self, name: &N) -> bool {
+        let bit = Self::bit(name);
+        let removed = self.bits & bit != 0;
+        self.bits &= !bit;
+        removed
+    }
+
+    /// Removes all variants from the set.
+    pub fn clear(&mut self) {
+        self.bits = 0;
+    }
+
+    /// Returns the set with the variants contained in `self` or in `other`.
+    pub const fn union(self, other: Self) -> Self {
+        Self::from_bits(self.bits | other.bits)
+    }
+
+    /// Returns the set with the variants contained both in `self` and in `other`.
+    pub const fn intersection(self, other: Self) -> Self {
+        Self::from_bits(self.bits & other.bits)
+    }
+
+    /// Returns the set with the variants contained in `self`, but not in `other`.
+    pub const fn difference(self, other: Self) -> Self {
+        Self::from_bits(self.bits & !other.bits)
+    }
+
+    /// Returns the set with the variants contained in `self` or in `other`, but not in both.
+    pub const fn symmetric_difference(self, other: Self) -> Self {
+        Self::from_bits(self.bits ^ other.bits)
+    }
+
+    /// Returns the set with the variants not contained in `self`.
+    pub const fn complement(self) -> Self {
+        Self::from_bits(!self.bits & Self::ALL.bits)
+    }
+
+    /// Returns `true` if all variants of `self` are contained in `other`.
+    pub const fn is_subset(&self, other: &Self) -> bool {
+        self.bits & !other.bits == 0
+    }
+
+    /// Returns `true` if all variants of `other` are contained in `self`.
+    pub const fn is_superset(&self, other: &Self) -> bool {
+        other.is_subset(self)
+    }
+
+    /// Returns `true` if `self` and `other` have no variants in common.
+    pub const fn is_disjoint(&self, other: &Self) -> bool {
+        self.bits & other.bits == 0
+    }
+
+    /// Returns an iterator over the variants of the set, in the order of the fields declaration.
+    pub fn iter(&self) -> FieldNameSetIter<N> {
+        FieldNameSetIter {
+            bits: self.bits,
+            _name: PhantomData,
+        }
+    }
+}
+
+impl<N> Clone for FieldNameSet<N> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<N> Copy for FieldNameSet<N> {}
+
+impl<N> PartialEq for FieldNameSet<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.bits == other.bits
+    }
+}
+
+impl<N> Eq for FieldNameSet<N> {}
+
+impl<N> Hash for FieldNameSet<N> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.bits.hash(state);
+    }
+}
+
+impl<N: FieldName> Default for FieldNameSet<N> {
+    fn default() -> Self {
+        Self::EMPTY
+    }
+}
+
+impl<N: FieldName> fmt::Debug for FieldNameSet<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set()
+            .entries(self.iter().map(|name| name.name()))
+            .finish()
+    }
+}
+
+impl<N: FieldName> From<N> for FieldNameSet<N> {
+    fn from(name: N) -> Self {
+        Self::from_bits(Self::bit(&name))
+    }
+}
+
+impl<N: FieldName> FromIterator<N> for FieldNameSet<N> {
+    fn from_iter<I: IntoIterator<Item = N>>(iter: I) -> Self {
+        let mut set = Self::EMPTY;
+        set.extend(iter);
+        set
+    }
+}
+
+impl<N: FieldName> Extend<N> for FieldNameSet<N> {
+    fn extend<I: IntoIterator<Item = N>>(&mut self, iter: I) {
+        for name in iter {
+            self.insert(name);
+        }
+    }
+}
+
+impl<N: FieldName> IntoIterator for FieldNameSet<N> {
+    type Item = N;
+    type IntoIter = FieldNameSetIter<N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<N: FieldName> IntoIterator for &FieldNameSet<N> {
+    type Item = N;
+    type IntoIter = FieldNameSetIter<N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<N: FieldName> BitOr for FieldNameSet<N> {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        self.union(other)
+    }
+}
+
+impl<N: FieldName> BitOrAssign for FieldNameSet<N> {
+    fn bitor_assign(&mut self, other: Self) {
+        *self = self.union(other);
+    }
+}
+
+impl<N: FieldName> BitAnd for FieldNameSet<N> {
+    type Output = Self;
+
+    fn bitand(self, other: Self) -> Self {
+        self.intersection(other)
+    }
+}
+
+impl<N: FieldName> BitAndAssign for FieldNameSet<N> {
+    fn bitand_assign(&mut self, other: Self) {
+        *self = self.intersection(other);
+    }
+}
+
+impl<N: FieldName> Sub for FieldNameSet<N> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self.difference(other)
+    }
+}
+
+impl<N: FieldName> SubAssign for FieldNameSet<N> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = self.difference(other);
+    }
+}
+
+/// An iterator over the variants of a [`FieldNameSet`].
+pub struct FieldNameSetIter<N> {
+    bits: u128,
+    _name: PhantomData<fn() -> N>,
+}
+
+impl<N> Clone for FieldNameSetIter<N> {
+    fn clone(&self) -> Self {
+        Self {
+            bits: self.bits,
+            _name: PhantomData,
+        }
+    }
+}
+
+impl<N: FieldName> Iterator for FieldNameSetIter<N> {
+    type Item = N;
+
+    fn next(&mut self) -> Option<N> {
+        if self.bits == 0 {
+            return None;
+        }
+        let index = self.bits.trailing_zeros();
+        self.bits &= !(1 << index);
+        N::from_index(index as usize)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.bits.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl<N: FieldName> DoubleEndedIterator for FieldNameSetIter<N> {
+    fn next_back(&mut self) -> Option<N> {
+        if self.bits == 0 {
+            return None;
+        }
+        let index = 127 - self.bits.leading_zeros();
+        self.bits &= !(1 << index);
+        N::from_index(index as usize)
+    }
+}
+
+impl<N: FieldName> ExactSizeIterator for FieldNameSetIter<N> {}
+
+impl<N: FieldName> FusedIterator for FieldNameSetIter<N> {}
@@ -0,0 +1,71 @@
+#![allow(dead_code)]
+
+extern crate field_types;
+
+use field_types::FieldName;
+
+#[derive(FieldName)]
+#[field_name(any)]
+struct Test {
+    first: i32,
+    second_field: Option<String>,
+    #[field_name(skip)]
+    third: bool,
+}
+
+#[derive(FieldName)]
+#[field_types(any)]
+struct TestGen<T> {
+    first: T,
+    second_field: String,
+}
+
+#[test]
+fn get_any() {
+    let test = Test {
+        first: 1,
+        second_field: Some("test".to_string()),
+        third: true,
+    };
+
+    assert_eq!(Some(&1), test.get_any(TestFieldName::First).downcast_ref::<i32>());
+    assert_eq!(None, test.get_any(TestFieldName::First).downcast_ref::<u32>());
+    assert_eq!(
+        Some(&Some("test".to_string())),
+        test.get_any(TestFieldName::SecondField).downcast_ref::<Option<String>>()
+    );
+}
+
+#[test]
+fn get_any_mut() {
+    let mut test = TestGen {
+        first: 1.5,
+        second_field: "test".to_string(),
+    };
+
+    *test.get_any_mut(TestGenFieldName::First).downcast_mut::<f64>().unwrap() = 2.5;
+    test.get_any_mut(TestGenFieldName::SecondField).downcast_mut::<String>().unwrap().push('!');
+
+    assert_eq!(2.5, test.first);
+    assert_eq!("test!", test.second_field);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn set_any() {
+    let mut test = Test {
+        first: 1,
+        second_field: None,
+        third: true,
+    };
+
+    assert_eq!(Ok(()), test.set_any(TestFieldName::First, Box::new(2)));
+    assert_eq!(Ok(()), test.set_any(TestFieldName::SecondField, Box::new(Some("test".to_string()))));
+    assert_eq!(2, test.first);
+    assert_eq!(Some("test".to_string()), test.second_field);
+
+    let error = test.set_any(TestFieldName::First, Box::new("2")).unwrap_err();
+    assert_eq!(field_types::TypeMismatch::new("first", "i32"), error);
+    assert_eq!("type mismatch of field `first`, expected value of type `i32`", error.to_string());
+    assert_eq!(2, test.first);
+}
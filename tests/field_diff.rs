@@ -0,0 +1,64 @@
+#![allow(dead_code)]
+
+extern crate field_types;
+
+use field_types::{FieldName, FieldType};
+
+#[derive(FieldName, FieldType)]
+#[field_types(diff)]
+#[field_type_derive(Debug, PartialEq)]
+struct Test {
+    first: i32,
+    second_field: Option<String>,
+    #[field_types(skip)]
+    third: std::sync::Mutex<u8>,
+}
+
+#[derive(FieldName, FieldType, Clone)]
+#[field_types(diff)]
+#[field_type_derive(Debug, PartialEq)]
+struct TestGen<T> {
+    first: T,
+    second_field: Vec<T>,
+    third: u8,
+}
+
+#[test]
+fn changed_fields() {
+    let a = TestGen { first: 1, second_field: vec![1, 2], third: 3 };
+    let mut b = a.clone();
+    assert!(a.changed_fields(&b).is_empty());
+
+    b.second_field.push(3);
+    b.third = 4;
+    let changed = a.changed_fields(&b);
+    assert_eq!(2, changed.len());
+    assert!(changed.contains(&TestGenFieldName::SecondField));
+    assert!(!changed.contains(&TestGenFieldName::First));
+    assert_eq!(r#"{"second_field", "third"}"#, format!("{:?}", changed));
+}
+
+#[test]
+fn diff() {
+    let a = TestGen { first: 1, second_field: vec![1, 2], third: 3 };
+    let b = TestGen { first: 2, second_field: vec![1, 2], third: 4 };
+
+    assert_eq!(0, a.diff(&a).count());
+    assert_eq!(
+        vec![TestGenFieldType::First(2), TestGenFieldType::Third(4)],
+        a.diff(&b).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![TestGenFieldType::First(1), TestGenFieldType::Third(3)],
+        b.diff(&a).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn diff_skipped() {
+    let a = Test { first: 1, second_field: None, third: Default::default() };
+    let b = Test { first: 1, second_field: Some("test".to_string()), third: Default::default() };
+
+    assert_eq!(TestFieldNameSet::from(TestFieldName::SecondField), a.changed_fields(&b));
+    assert_eq!(vec![TestFieldType::SecondField(Some("test".to_string()))], a.diff(&b).collect::<Vec<_>>());
+}
@@ -0,0 +1,151 @@
+#![allow(dead_code)]
+
+extern crate field_types;
+
+use field_types::{FieldName, FieldNames, FieldType, FieldTypes};
+
+#[derive(FieldName, FieldType, Clone)]
+#[field_type_derive(Debug, PartialEq)]
+struct Header {
+    /// Message version
+    version: u8,
+    id: String,
+}
+
+#[derive(FieldName, FieldType)]
+#[field_type_derive(Debug, PartialEq)]
+struct Message<T> {
+    kind: u32,
+    #[field_types(flatten)]
+    header: Header,
+    body: T,
+    #[field_types(skip)]
+    checksum: u64,
+}
+
+fn message() -> Message<bool> {
+    Message {
+        kind: 1,
+        header: Header {
+            version: 2,
+            id: "test".to_string(),
+        },
+        body: true,
+        checksum: 0,
+    }
+}
+
+#[test]
+fn flatten_field_name() {
+    assert_eq!(4, <MessageFieldName as FieldName>::COUNT);
+    assert_eq!(4, <Message<bool> as FieldNames>::COUNT);
+    assert_eq!(
+        [
+            MessageFieldName::Kind,
+            MessageFieldName::Header(HeaderFieldName::Version),
+            MessageFieldName::Header(HeaderFieldName::Id),
+            MessageFieldName::Body,
+        ],
+        Message::<bool>::as_field_name_array()
+    );
+
+    let names: Vec<_> = Message::<bool>::field_names().map(|name| name.name()).collect();
+    assert_eq!(vec!["kind", "version", "id", "body"], names);
+
+    assert_eq!(Some(MessageFieldName::Header(HeaderFieldName::Id)), MessageFieldName::by_name("id"));
+    assert_eq!(None, MessageFieldName::by_name("header"));
+    assert_eq!(None, MessageFieldName::by_name("checksum"));
+
+    assert_eq!(2, MessageFieldName::Header(HeaderFieldName::Id).index());
+    assert_eq!(3, MessageFieldName::Body.index());
+    assert_eq!(Some(MessageFieldName::Header(HeaderFieldName::Version)), MessageFieldName::from_index(1));
+    assert_eq!(None, MessageFieldName::from_index(4));
+
+    assert_eq!(Some("Message version"), MessageFieldName::Header(HeaderFieldName::Version).description());
+    assert_eq!("String", MessageFieldName::Header(HeaderFieldName::Id).type_name());
+    assert_eq!("T", MessageFieldName::Body.type_name());
+}
+
+#[test]
+fn flatten_field_name_collections() {
+    let mut set = MessageFieldNameSet::empty();
+    set.insert(MessageFieldName::Header(HeaderFieldName::Id));
+    assert_eq!(r#"{"id"}"#, format!("{:?}", set));
+
+    let map = MessageFieldNameMap::from_fn(|name| name.name().len());
+    assert_eq!([4, 7, 2, 4], map.into_array());
+}
+
+#[test]
+fn flatten_field_type() {
+    assert_eq!(4, <Message<bool> as FieldTypes>::COUNT);
+    assert_eq!(
+        [
+            MessageFieldType::Kind(1),
+            MessageFieldType::Header(HeaderFieldType::Version(2)),
+            MessageFieldType::Header(HeaderFieldType::Id("test".to_string())),
+            MessageFieldType::Body(true),
+        ],
+        message().into_field_type_array()
+    );
+
+    let fields: [MessageFieldType<bool>; 4] = message().into();
+    assert_eq!(MessageFieldType::Body(true), fields[3]);
+
+    let values: Vec<_> = message().into_iter().rev().collect();
+    assert_eq!(MessageFieldType::Kind(1), values[3]);
+}
+
+#[derive(FieldName, FieldType)]
+#[field_type_derive(Debug, PartialEq)]
+struct Exchange {
+    #[field_types(flatten(prefix = "request_"))]
+    request: Header,
+    #[field_types(flatten(prefix = "response_"))]
+    response: Header,
+    status: u16,
+}
+
+#[derive(FieldName, FieldType)]
+struct Log {
+    #[field_name(flatten(prefix = "last_"))]
+    #[field_type(flatten)]
+    exchange: Exchange,
+}
+
+#[test]
+fn flatten_prefix_field_name() {
+    assert_eq!(
+        ["request_version", "request_id", "response_version", "response_id", "status"],
+        <ExchangeFieldName as FieldName>::NAMES
+    );
+    assert_eq!("response_id", ExchangeFieldName::Response(HeaderFieldName::Id).name());
+    assert_eq!("status", ExchangeFieldName::Status.name());
+    assert_eq!(Some(ExchangeFieldName::Request(HeaderFieldName::Version)), ExchangeFieldName::by_name("request_version"));
+    assert_eq!(Some(ExchangeFieldName::Response(HeaderFieldName::Id)), ExchangeFieldName::by_name("response_id"));
+    assert_eq!(None, ExchangeFieldName::by_name("id"));
+    assert_eq!(None, ExchangeFieldName::by_name("request_status"));
+
+    let name = LogFieldName::Exchange(ExchangeFieldName::Request(HeaderFieldName::Id));
+    assert_eq!("last_request_id", name.name());
+    assert_eq!(Some(name), LogFieldName::by_name("last_request_id"));
+    assert_eq!(Some("Message version"), ExchangeFieldName::Response(HeaderFieldName::Version).description());
+}
+
+#[test]
+fn flatten_prefix_field_type() {
+    assert_eq!(["kind", "version", "id", "body"], <Message<bool> as FieldTypes>::NAMES);
+    assert_eq!(
+        ["request_version", "request_id", "response_version", "response_id", "status"],
+        <Exchange as FieldTypes>::NAMES
+    );
+    // The prefix of `FieldName` derive doesn't apply to `FieldType` derive
+    assert_eq!(<Exchange as FieldTypes>::NAMES, <Log as FieldTypes>::NAMES);
+}
+
+#[test]
+#[should_panic(expected = "the field names of the flattened fields must be unique")]
+fn flatten_duplicate_names() {
+    // The derives evaluate the table at compile time, so a duplicate name fails the compilation
+    let _: [&str; 3] = field_types::__private::concat_names(&[&["id"], <HeaderFieldName as FieldName>::NAMES]);
+}
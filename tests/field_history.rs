@@ -0,0 +1,131 @@
+#![cfg(feature = "alloc")]
+#![allow(dead_code)]
+
+extern crate field_types;
+
+use field_types::{FieldType, History, SetField};
+
+#[derive(FieldType, Clone, Debug, PartialEq)]
+#[field_type(history)]
+#[field_type_derive(Debug, Clone, PartialEq)]
+struct Test {
+    first: i32,
+    second_field: Option<String>,
+    #[field_type(skip)]
+    third: bool,
+}
+
+#[derive(FieldType, Debug, PartialEq)]
+#[field_type(history)]
+#[field_type_derive(Debug, PartialEq)]
+struct TestGen<'a, T, const N: usize> {
+    first: [T; N],
+    second_field: &'a str,
+}
+
+#[derive(FieldType, Debug, PartialEq)]
+#[field_type(history)]
+#[field_type_derive(Debug, PartialEq)]
+struct TestFlatten {
+    #[field_type(flatten)]
+    test: Test,
+    fourth: u8,
+}
+
+fn test() -> Test {
+    Test {
+        first: 1,
+        second_field: Some("test".to_string()),
+        third: true,
+    }
+}
+
+#[test]
+fn set_field() {
+    let mut test = test();
+    assert_eq!(TestFieldType::First(1), test.set_field(TestFieldType::First(2)));
+    assert_eq!(TestFieldType::SecondField(Some("test".to_string())), test.set_field(TestFieldType::SecondField(None)));
+    assert_eq!(Test { first: 2, second_field: None, third: true }, test);
+
+    let mut test = TestFlatten { test: self::test(), fourth: 4 };
+    assert_eq!(
+        TestFlattenFieldType::Test(TestFieldType::First(1)),
+        SetField::set_field(&mut test, TestFlattenFieldType::Test(TestFieldType::First(3)))
+    );
+    assert_eq!(TestFlattenFieldType::Fourth(4), test.set_field(TestFlattenFieldType::Fourth(5)));
+    assert_eq!(3, test.test.first);
+    assert_eq!(5, test.fourth);
+}
+
+#[test]
+fn history_undo_redo() {
+    let mut test = test();
+    let mut history = TestHistory::new();
+    assert!(!history.can_undo());
+    assert!(!history.undo(&mut test));
+
+    history.set(&mut test, TestFieldType::First(2));
+    history.set(&mut test, TestFieldType::First(3));
+    history.checkpoint();
+    history.set(&mut test, TestFieldType::SecondField(None));
+    assert!(history.can_undo());
+
+    assert!(history.undo(&mut test));
+    assert_eq!(Test { first: 3, ..self::test() }, test);
+    assert!(history.undo(&mut test));
+    assert_eq!(self::test(), test);
+    assert!(!history.undo(&mut test));
+    assert!(history.can_redo());
+
+    assert!(history.redo(&mut test));
+    assert_eq!(Test { first: 3, ..self::test() }, test);
+    assert!(history.redo(&mut test));
+    assert_eq!(Test { first: 3, second_field: None, third: true }, test);
+    assert!(!history.redo(&mut test));
+
+    history.undo(&mut test);
+    history.set(&mut test, TestFieldType::First(4));
+    assert!(!history.can_redo());
+    history.undo(&mut test);
+    assert_eq!(Test { first: 3, ..self::test() }, test);
+
+    history.clear();
+    assert!(!history.can_undo());
+}
+
+#[test]
+fn history_capacity() {
+    let mut test = test();
+    let mut history = History::with_capacity(2);
+    assert_eq!(Some(2), history.capacity());
+    for value in 2..=4 {
+        history.set(&mut test, TestFieldType::First(value));
+        history.checkpoint();
+    }
+    assert!(history.undo(&mut test));
+    assert!(history.undo(&mut test));
+    assert!(!history.undo(&mut test));
+    assert_eq!(2, test.first);
+
+    let mut history = TestHistory::with_capacity(0);
+    history.set(&mut test, TestFieldType::First(5));
+    assert!(!history.can_undo());
+    assert!(!history.undo(&mut test));
+    assert_eq!(5, test.first);
+
+    let mut history = TestHistory::default();
+    let mut cloned = history.clone();
+    history.set(&mut test, TestFieldType::First(5));
+    assert!(!cloned.undo(&mut test));
+    assert_eq!(None, history.capacity());
+}
+
+#[test]
+fn history_generic() {
+    let mut test = TestGen { first: [1, 2], second_field: "test" };
+    let mut history: TestGenHistory<'_, i32, 2> = History::new();
+    history.set(&mut test, TestGenFieldType::First([3, 4]));
+    history.set(&mut test, TestGenFieldType::SecondField("changed"));
+    history.undo(&mut test);
+    assert_eq!(TestGen { first: [1, 2], second_field: "test" }, test);
+}
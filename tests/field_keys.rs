@@ -0,0 +1,95 @@
+#![allow(dead_code)]
+
+extern crate field_types;
+
+use std::fmt::Debug;
+use field_types::{Field, FieldName};
+
+#[derive(FieldName)]
+#[field_name(field_keys)]
+struct Test {
+    first: i32,
+    /// Second field
+    second_field: Option<String>,
+    #[field_name(skip)]
+    third: bool,
+}
+
+#[derive(FieldName)]
+#[field_types(field_keys)]
+struct TestGen<'a, T: Debug, const N: usize> {
+    first: T,
+    second_field: &'a str,
+    third: [u8; N],
+}
+
+mod foo {
+    use field_types::{Field, FieldName};
+
+    #[derive(FieldName)]
+    #[field_name(field_keys)]
+    pub struct Value {
+        pub name: String,
+        value: String,
+    }
+
+    // The marker of the private field is visible only in the module of the struct
+    pub fn value_field_name() -> &'static str {
+        value_fields::Value::NAME
+    }
+
+    #[derive(FieldName)]
+    #[field_name(field_keys)]
+    pub(super) struct Restricted {
+        pub(super) value: u8,
+    }
+}
+
+fn take<F: Field<Type = Option<String>>>(owner: &mut F::Owner, _field: F) -> Option<String> {
+    F::get_mut(owner).take()
+}
+
+fn describe<F: Field>(owner: &F::Owner) -> String
+    where F::Type: Debug
+{
+    format!("{} = {:?}", F::NAME, F::get(owner))
+}
+
+#[test]
+fn field_keys() {
+    let mut test = Test {
+        first: 1,
+        second_field: Some("test".to_string()),
+        third: true,
+    };
+
+    assert_eq!("first", test_fields::First::NAME);
+    assert_eq!(&1, test_fields::First::get(&test));
+    *test_fields::First::get_mut(&mut test) = 2;
+    assert_eq!("first = 2", describe::<test_fields::First>(&test));
+
+    assert_eq!(Some("test".to_string()), take(&mut test, test_fields::SecondField));
+    assert_eq!(None, test.second_field);
+}
+
+#[test]
+fn field_keys_gen() {
+    let test = TestGen {
+        first: 1.5,
+        second_field: "test",
+        third: [1, 2],
+    };
+
+    assert_eq!(&1.5, test_gen_fields::First::get(&test));
+    assert_eq!("second_field = \"test\"", describe::<test_gen_fields::SecondField<f64, 2>>(&test));
+    assert_eq!(&[1, 2], <test_gen_fields::Third<f64, 2>>::get(&test));
+
+    let _ = test_gen_fields::First::<f64, 2>::default();
+}
+
+#[test]
+fn field_keys_private() {
+    assert_eq!("name", foo::value_fields::Name::NAME);
+    assert_eq!("value", foo::value_field_name());
+    assert_eq!("value", foo::restricted_fields::Value::NAME);
+}
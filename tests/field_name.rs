@@ -1,4 +1,5 @@
 #![allow(dead_code)]
+#![allow(clippy::match_like_matches_macro)]
 
 extern crate variant_count;
 extern crate field_types;
@@ -43,6 +44,61 @@ struct TestNameDerive {
     second: bool,
 }
 
+#[derive(FieldName)]
+struct TestConstGen<T, const N: usize> {
+    first: [T; N],
+    second: usize,
+}
+
+#[derive(FieldName, Debug, PartialEq)]
+#[field_name(index)]
+struct Rgb {
+    r: u8,
+    g: u8,
+    b: u8,
+}
+
+#[derive(FieldName, Debug, PartialEq)]
+#[field_types(index)]
+struct TestIndexGen<T> {
+    first: T,
+    second: T,
+    #[field_name(skip)]
+    count: usize,
+}
+
+#[derive(Debug, PartialEq)]
+struct NoDefault;
+
+#[derive(FieldName, Debug, PartialEq)]
+#[field_name(index)]
+struct TestIndexNoDefault {
+    first: u8,
+    second: u8,
+    #[field_name(skip)]
+    marker: NoDefault,
+}
+
+#[derive(FieldName, Debug, PartialEq)]
+#[field_name(map)]
+struct TestMap<T: Clone> {
+    first: T,
+    second: T,
+    #[field_name(skip)]
+    label: &'static str,
+}
+
+#[derive(FieldName)]
+struct TestDoc {
+    /// The first field.
+    first: i32,
+    /// The second field.
+    ///
+    /// With a second paragraph.
+    second: bool,
+    third: bool,
+}
+
 #[test]
 fn full_field_name_variants() {
     let _field = TestFieldName::First;
@@ -138,3 +194,86 @@ fn field_name_str() {
     assert_eq!(Some(TestFieldName::SecondField), TestFieldName::by_name("second_field"));
     assert_eq!(None, TestFieldName::by_name("third"));
 }
+
+#[test]
+fn field_name_description() {
+    assert_eq!(TestDocFieldName::First.description(), Some("The first field."));
+    assert_eq!(TestDocFieldName::Second.description(), Some("The second field.\n\nWith a second paragraph."));
+    assert_eq!(TestDocFieldName::Third.description(), None);
+
+    assert_eq!(TestFieldName::First.description(), None);
+}
+
+fn convert<S, A: From<S>>(source: S) -> A {
+    A::from(source)
+}
+
+#[test]
+fn from_field_name() {
+    let message = "test".to_string();
+    let test = TestGen {
+        first: 1,
+        second_field: Some(&message),
+        third: &2,
+        fourth: message.clone(),
+    };
+    let fields: [TestGenFieldName; 2] = convert(&test);
+    assert_eq!(TestGenFieldName::First, fields[0]);
+    assert_eq!(TestGenFieldName::SecondField, fields[1]);
+
+    let test = TestConstGen { first: [1, 2, 3], second: 3 };
+    let fields: [TestConstGenFieldName; 2] = convert(&test);
+    assert_eq!([TestConstGenFieldName::First, TestConstGenFieldName::Second], fields);
+    assert_eq!(fields, TestConstGen::<i32, 3>::as_field_name_array());
+}
+
+#[test]
+fn iter_field_name() {
+    let names = Test::field_names();
+    assert_eq!(2, names.len());
+    assert_eq!(vec![TestFieldName::SecondField, TestFieldName::First], names.rev().collect::<Vec<_>>());
+
+    let names = TestTypesDerive::field_names()
+        .map(|name| name.name())
+        .collect::<Vec<_>>();
+    assert_eq!(vec!["first", "second"], names);
+}
+
+#[test]
+fn index_field_name() {
+    let mut color = Rgb { r: 1, g: 2, b: 3 };
+    assert_eq!(2, color[RgbFieldName::G]);
+    color[RgbFieldName::B] = 4;
+    for name in Rgb::field_names() {
+        color[name] += 1;
+    }
+    assert_eq!(Rgb { r: 2, g: 3, b: 5 }, color);
+    assert_eq!([2, 3, 5], color.into_array());
+    assert_eq!(Rgb { r: 0, g: 1, b: 2 }, Rgb::from_array([0, 1, 2]));
+
+    let mut test = TestIndexGen { first: "a", second: "b", count: 2 };
+    test[TestIndexGenFieldName::First] = "c";
+    assert_eq!("b", test[TestIndexGenFieldName::Second]);
+    assert_eq!(["c", "b"], test.into_array());
+    assert_eq!(TestIndexGen { first: "a", second: "b", count: 0 }, TestIndexGen::from_array(["a", "b"]));
+
+    // `from_array` is not available, but the other methods are
+    let mut test = TestIndexNoDefault { first: 1, second: 2, marker: NoDefault };
+    test[TestIndexNoDefaultFieldName::Second] = 3;
+    assert_eq!([1, 3], test.into_array());
+}
+
+#[test]
+fn map_field_name() {
+    let test = TestMap { first: 1, second: 2, label: "test" };
+    let mapped = test.map(|name, value| format!("{}={}", name.name(), value));
+    assert_eq!(TestMap { first: "first=1".to_string(), second: "second=2".to_string(), label: "test" }, mapped);
+
+    let left = TestMap { first: 1, second: 2, label: "left" };
+    let right = TestMap { first: 0.5, second: 1.5, label: "right" };
+    let zipped = left.zip_with(right, |name, left, right| match name {
+        TestMapFieldName::First => left as f64 + right,
+        TestMapFieldName::Second => left as f64 - right,
+    });
+    assert_eq!(TestMap { first: 1.5, second: 0.5, label: "left" }, zipped);
+}
@@ -0,0 +1,64 @@
+#![allow(dead_code)]
+
+extern crate field_types;
+
+use field_types::FieldName;
+
+#[derive(FieldName)]
+struct Test {
+    first: i32,
+    second_field: Option<String>,
+    #[field_name(skip)]
+    third: bool,
+    fourth: bool,
+}
+
+#[test]
+fn field_name_map_from_fn() {
+    let map = TestFieldNameMap::from_fn(|name| name.name().len());
+    assert_eq!(3, map.len());
+    assert_eq!(5, map[TestFieldName::First]);
+    assert_eq!(12, map[TestFieldName::SecondField]);
+    assert_eq!(&6, map.get(&TestFieldName::Fourth));
+    assert_eq!([5, 12, 6], map.into_array());
+
+    let map: TestFieldNameMap<Option<&str>> = Default::default();
+    assert!(map.values().all(Option::is_none));
+}
+
+#[test]
+fn field_name_map_mutate() {
+    let mut map = TestFieldNameMap::from_array([0, 0, 0]);
+    map[TestFieldName::SecondField] += 2;
+    *map.get_mut(&TestFieldName::Fourth) = 4;
+    assert_eq!(0, map.insert(&TestFieldName::First, 1));
+    assert_eq!(&[1, 2, 4], map.as_array());
+
+    for (name, value) in map.iter_mut() {
+        if name == TestFieldName::First {
+            *value = 10;
+        }
+    }
+    for value in map.values_mut() {
+        *value += 1;
+    }
+    assert_eq!(vec![(TestFieldName::First, &11), (TestFieldName::SecondField, &3), (TestFieldName::Fourth, &5)],
+               map.iter().collect::<Vec<_>>());
+}
+
+#[test]
+fn field_name_map_map() {
+    let map = TestFieldNameMap::from_array([1, 2, 3]);
+    let labels = map.map(|name, value| format!("{}={}", name.name(), value));
+    assert_eq!("second_field=2", labels[TestFieldName::SecondField]);
+
+    let pairs: Vec<_> = labels.clone().into_iter().collect();
+    assert_eq!(vec![
+        (TestFieldName::First, "first=1".to_string()),
+        (TestFieldName::SecondField, "second_field=2".to_string()),
+        (TestFieldName::Fourth, "fourth=3".to_string()),
+    ], pairs);
+
+    assert_eq!(labels, labels.clone());
+    assert_eq!(r#"{"first": 1, "second_field": 2, "fourth": 3}"#, format!("{:?}", map));
+}
@@ -0,0 +1,92 @@
+#![allow(dead_code)]
+
+extern crate field_types;
+
+use std::collections::HashSet;
+use field_types::{FieldName, FieldNameSet};
+
+#[derive(FieldName)]
+struct Test {
+    first: i32,
+    second_field: Option<String>,
+    #[field_name(skip)]
+    third: bool,
+    fourth: bool,
+}
+
+#[derive(FieldName)]
+#[field_name(name = "TestKey")]
+#[field_name_derive(Debug, PartialEq)]
+struct TestRenamed {
+    first: i32,
+    second: bool,
+}
+
+#[test]
+fn field_name_set_consts() {
+    assert!(TestFieldNameSet::EMPTY.is_empty());
+    assert_eq!(0, TestFieldNameSet::empty().len());
+    assert_eq!(TestFieldNameSet::default(), TestFieldNameSet::empty());
+
+    assert_eq!(3, TestFieldNameSet::ALL.len());
+    assert!(TestFieldNameSet::all().is_all());
+    assert_eq!(Test::field_names().collect::<Vec<_>>(), TestFieldNameSet::all().iter().collect::<Vec<_>>());
+}
+
+#[test]
+fn field_name_set_insert_remove() {
+    let mut set = TestFieldNameSet::empty();
+    assert!(set.insert(TestFieldName::Fourth));
+    assert!(set.insert(TestFieldName::First));
+    assert!(!set.insert(TestFieldName::First));
+    assert_eq!(2, set.len());
+    assert!(set.contains(&TestFieldName::First));
+    assert!(!set.contains(&TestFieldName::SecondField));
+    assert_eq!(vec![TestFieldName::First, TestFieldName::Fourth], set.iter().collect::<Vec<_>>());
+    assert_eq!(vec![TestFieldName::Fourth, TestFieldName::First], set.iter().rev().collect::<Vec<_>>());
+
+    assert!(set.remove(&TestFieldName::First));
+    assert!(!set.remove(&TestFieldName::First));
+    assert_eq!(TestFieldNameSet::from(TestFieldName::Fourth), set);
+
+    set.clear();
+    assert!(set.is_empty());
+}
+
+#[test]
+fn field_name_set_algebra() {
+    let left: TestFieldNameSet = vec![TestFieldName::First, TestFieldName::SecondField].into_iter().collect();
+    let right: TestFieldNameSet = [TestFieldName::SecondField, TestFieldName::Fourth].iter().copied().collect();
+
+    assert_eq!(TestFieldNameSet::ALL, left.union(right));
+    assert_eq!(TestFieldNameSet::ALL, left | right);
+    assert_eq!(TestFieldNameSet::from(TestFieldName::SecondField), left.intersection(right));
+    assert_eq!(TestFieldNameSet::from(TestFieldName::SecondField), left & right);
+    assert_eq!(TestFieldNameSet::from(TestFieldName::First), left.difference(right));
+    assert_eq!(TestFieldNameSet::from(TestFieldName::First), left - right);
+    assert_eq!(right - left, left.complement());
+    assert_eq!(left.symmetric_difference(right), (left - right) | (right - left));
+
+    assert!(left.intersection(right).is_subset(&left));
+    assert!(left.is_superset(&left.intersection(right)));
+    assert!(left.difference(right).is_disjoint(&right));
+
+    let mut set = left;
+    set |= right;
+    set -= TestFieldNameSet::from(TestFieldName::Fourth);
+    set &= right;
+    assert_eq!(vec![TestFieldName::SecondField], set.into_iter().collect::<Vec<_>>());
+
+    let mut hashes = HashSet::new();
+    hashes.insert(left);
+    assert!(hashes.contains(&left));
+}
+
+#[test]
+fn field_name_set_debug() {
+    let set: TestFieldNameSet = vec![TestFieldName::First, TestFieldName::Fourth].into_iter().collect();
+    assert_eq!(r#"{"first", "fourth"}"#, format!("{:?}", set));
+
+    let set: FieldNameSet<TestKey> = TestKeySet::all();
+    assert_eq!(r#"{"first", "second"}"#, format!("{:?}", set));
+}
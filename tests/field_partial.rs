@@ -0,0 +1,99 @@
+#![allow(dead_code)]
+
+extern crate field_types;
+
+use std::convert::TryFrom;
+use field_types::{FieldName, MissingFields};
+
+#[derive(FieldName, Debug, PartialEq)]
+#[field_name(partial(Debug, Clone, PartialEq))]
+struct Test {
+    first: i32,
+    /// Second field
+    second_field: Option<String>,
+    #[field_name(skip)]
+    third: bool,
+}
+
+#[derive(FieldName, Debug, PartialEq)]
+#[field_types(partial)]
+struct TestGen<T, U> {
+    first: T,
+    second_field: String,
+    #[field_types(skip)]
+    third: Vec<U>,
+}
+
+#[derive(Debug, PartialEq)]
+struct NoDefault;
+
+#[derive(FieldName, Debug, PartialEq)]
+#[field_name(partial)]
+struct TestNoDefault {
+    first: i32,
+    #[field_name(skip)]
+    marker: NoDefault,
+}
+
+#[test]
+fn partial_apply() {
+    let mut test = Test {
+        first: 1,
+        second_field: None,
+        third: true,
+    };
+
+    let partial = TestPartial {
+        second_field: Some(Some("test".to_string())),
+        ..Default::default()
+    };
+    assert!(!partial.is_empty());
+    assert!(TestPartial::default().is_empty());
+
+    test.apply(partial);
+    assert_eq!(Test { first: 1, second_field: Some("test".to_string()), third: true }, test);
+
+    assert_eq!(
+        TestPartial { first: Some(1), second_field: Some(Some("test".to_string())) },
+        test.into_partial()
+    );
+}
+
+#[test]
+fn partial_merge() {
+    let base = TestPartial {
+        first: Some(1),
+        second_field: Some(None),
+    };
+    let layer = TestPartial {
+        first: Some(2),
+        second_field: None,
+    };
+    assert_eq!(TestPartial { first: Some(2), second_field: Some(None) }, base.clone().merge(layer));
+    assert_eq!(base.clone(), base.merge(TestPartial::default()));
+}
+
+#[test]
+fn partial_try_from() {
+    let test = Test::try_from(TestPartial { first: Some(1), second_field: Some(None) }).unwrap();
+    assert_eq!(Test { first: 1, second_field: None, third: false }, test);
+
+    let error = Test::try_from(TestPartial { first: None, second_field: Some(None) }).unwrap_err();
+    assert!(error.fields().contains(&TestFieldName::First));
+    assert_eq!(1, error.fields().len());
+    assert_eq!("missing fields: `first`", error.to_string());
+
+    let error: MissingFields<TestGenFieldName> = TestGen::<f64, u8>::try_from(TestGenPartial::default()).unwrap_err();
+    assert_eq!("missing fields: `first`, `second_field`", error.to_string());
+
+    let test = TestGen::<f64, u8>::try_from(TestGenPartial { first: Some(1.5), second_field: Some("test".to_string()) }).unwrap();
+    assert_eq!(TestGen { first: 1.5, second_field: "test".to_string(), third: vec![] }, test);
+}
+
+#[test]
+fn partial_no_default() {
+    // `TryFrom` is not implemented, but the other methods are generated
+    let mut test = TestNoDefault { first: 1, marker: NoDefault };
+    test.apply(TestNoDefaultPartial { first: Some(2) });
+    assert_eq!(TestNoDefault { first: 2, marker: NoDefault }, test);
+}
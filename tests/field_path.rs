@@ -0,0 +1,116 @@
+#![allow(dead_code)]
+
+extern crate field_types;
+
+use field_types::{FieldName, FieldPath, FieldPaths, Lens};
+
+#[derive(FieldName)]
+#[field_name(field_keys)]
+struct Config {
+    name: String,
+    #[field_types(nested)]
+    server: Server,
+    #[field_name(nested)]
+    limits: Limits,
+}
+
+#[derive(FieldName)]
+#[field_name(field_keys)]
+struct Server {
+    host: String,
+    /// Server address
+    #[field_types(nested)]
+    address: Address,
+}
+
+#[derive(FieldName)]
+#[field_name(field_keys)]
+struct Address {
+    city: String,
+    port: u16,
+}
+
+#[derive(FieldName)]
+struct Limits {
+    max_connections: u32,
+    #[field_types(skip)]
+    cache: Vec<u8>,
+}
+
+fn config() -> Config {
+    Config {
+        name: "test".to_string(),
+        server: Server {
+            host: "localhost".to_string(),
+            address: Address {
+                city: "Paris".to_string(),
+                port: 8080,
+            },
+        },
+        limits: Limits {
+            max_connections: 10,
+            cache: Vec::new(),
+        },
+    }
+}
+
+fn path_names<T: FieldPaths>(paths: &[&str]) -> Vec<String> {
+    paths.iter()
+        .filter_map(|path| T::Path::by_path(path))
+        .map(|path| path.path().to_string())
+        .collect()
+}
+
+#[test]
+fn field_path_name() {
+    let path = ConfigFieldPath::Server(ServerFieldPath::Address(AddressFieldName::City));
+    assert_eq!(path.name(), "server.address.city");
+    assert_eq!("server.address.city", path.name().to_string());
+    assert_eq!("\"server.address.city\"", format!("{:?}", path.name()));
+    assert!(path.name() != "server.address");
+    assert!(path.name() != "server.address.city.name");
+    assert_eq!(ConfigFieldName::Server, path.field_name());
+
+    assert_eq!(ConfigFieldPath::Name.name(), "name");
+    assert_eq!(ConfigFieldPath::Limits(LimitsFieldName::MaxConnections).name(), "limits.max_connections");
+}
+
+#[test]
+fn field_by_path() {
+    assert_eq!(
+        Some(ConfigFieldPath::Server(ServerFieldPath::Address(AddressFieldName::Port))),
+        ConfigFieldPath::by_path("server.address.port")
+    );
+    assert_eq!(Some(ConfigFieldPath::Server(ServerFieldPath::Host)), ConfigFieldPath::by_path("server.host"));
+    assert_eq!(Some(ConfigFieldPath::Name), ConfigFieldPath::by_path("name"));
+    assert_eq!(None, ConfigFieldPath::by_path("server"));
+    assert_eq!(None, ConfigFieldPath::by_path("name.first"));
+    assert_eq!(None, ConfigFieldPath::by_path("server.address.street"));
+    assert_eq!(None, ConfigFieldPath::by_path("limits.cache"));
+
+    assert_eq!(vec!["server.address.city", "name"], path_names::<Config>(&["server.address.city", "server", "name"]));
+    assert_eq!(vec!["port"], path_names::<Address>(&["port", "address.port"]));
+}
+
+#[test]
+fn field_lens() {
+    let mut config = config();
+
+    let city = config_fields::Server.then(server_fields::Address).then(address_fields::City);
+    type Port = field_types::Then<field_types::Then<config_fields::Server, server_fields::Address>, address_fields::Port>;
+
+    assert_eq!(&8080, Port::get(&config));
+    assert_eq!(8080, Port::set(&mut config, 80));
+    assert_eq!(80, config.server.address.port);
+
+    let mut path = String::new();
+    Port::write_path(&mut path).unwrap();
+    assert_eq!("server.address.port", path);
+    assert_eq!(ConfigFieldPath::by_path(&path).unwrap().name(), "server.address.port");
+
+    fn set_city<L: Lens<Type = String>>(owner: &mut L::Owner, _lens: L, city: &str) {
+        L::get_mut(owner).replace_range(.., city);
+    }
+    set_city(&mut config, city, "Berlin");
+    assert_eq!("Berlin", config.server.address.city);
+}
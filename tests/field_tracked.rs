@@ -0,0 +1,115 @@
+#![allow(dead_code)]
+
+extern crate field_types;
+
+use field_types::{FieldName, FieldType};
+
+#[derive(FieldName, FieldType, Clone, Debug, PartialEq)]
+#[field_types(tracked(Debug, Clone))]
+#[field_type_derive(Debug, PartialEq)]
+struct Test {
+    first: i32,
+    second_field: Option<String>,
+    #[field_types(skip)]
+    third: bool,
+}
+
+#[derive(FieldName, FieldType)]
+#[field_types(tracked)]
+#[field_type_derive(Debug, PartialEq)]
+struct TestGen<'a, T: Clone> {
+    first: T,
+    second_field: &'a str,
+}
+
+#[derive(FieldName, FieldType)]
+#[field_name(tracked)]
+struct TestNamesOnly {
+    first: i32,
+}
+
+#[derive(FieldName, FieldType)]
+#[field_types(tracked)]
+#[field_type_derive(Debug, PartialEq)]
+struct TestTypeSkip {
+    first: i32,
+    #[field_type(skip)]
+    second: bool,
+}
+
+fn test() -> Test {
+    Test {
+        first: 1,
+        second_field: Some("test".to_string()),
+        third: true,
+    }
+}
+
+#[test]
+fn tracked_setters() {
+    let mut tracked = TestTracked::new(test());
+    assert!(tracked.changes().is_empty());
+    assert_eq!(1, tracked.first);
+
+    assert_eq!(1, tracked.set_first(2));
+    assert_eq!(2, tracked.first);
+    assert_eq!(TestFieldNameSet::from(TestFieldName::First), tracked.changes());
+
+    tracked.second_field_mut().take();
+    assert_eq!(TestFieldNameSet::all(), tracked.changes());
+
+    let expected = Test {
+        first: 2,
+        second_field: None,
+        third: true,
+    };
+    assert_eq!(&expected, tracked.get());
+    assert_eq!(expected, tracked.clone().into_inner());
+}
+
+#[test]
+fn tracked_take_changes() {
+    let mut tracked = TestTracked::from(test());
+    tracked.set_second_field(None);
+    assert_eq!(TestFieldNameSet::from(TestFieldName::SecondField), tracked.take_changes());
+    assert!(tracked.take_changes().is_empty());
+
+    // The unchanged value is recorded too
+    tracked.set_first(1);
+    assert_eq!(TestFieldNameSet::from(TestFieldName::First), tracked.take_changes());
+}
+
+#[test]
+fn tracked_changed_values() {
+    let mut tracked = TestTracked::new(test());
+    assert_eq!(0, tracked.take_changed_values().count());
+
+    tracked.set_first(3);
+    tracked.set_second_field(Some("changed".to_string()));
+    assert_eq!(
+        vec![TestFieldType::First(3), TestFieldType::SecondField(Some("changed".to_string()))],
+        tracked.take_changed_values().collect::<Vec<_>>()
+    );
+    assert!(tracked.changes().is_empty());
+    assert_eq!(0, tracked.take_changed_values().count());
+
+    let mut tracked = TestGenTracked::new(TestGen {
+        first: 1u8,
+        second_field: "test",
+    });
+    tracked.set_second_field("changed");
+    assert_eq!(
+        vec![TestGenFieldType::SecondField("changed")],
+        tracked.take_changed_values().collect::<Vec<_>>()
+    );
+
+    let mut tracked = TestNamesOnlyTracked::new(TestNamesOnly { first: 1 });
+    *tracked.first_mut() += 1;
+    assert_eq!(2, tracked.into_inner().first);
+
+    // The field skipped only by `FieldType` derive has no `..FieldType` value
+    let mut tracked = TestTypeSkipTracked::new(TestTypeSkip { first: 1, second: false });
+    tracked.set_first(2);
+    tracked.set_second(true);
+    assert_eq!(vec![TestTypeSkipFieldType::First(2)], tracked.take_changed_values().collect::<Vec<_>>());
+}
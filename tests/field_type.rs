@@ -1,4 +1,5 @@
 #![allow(dead_code)]
+#![allow(clippy::match_like_matches_macro)]
 
 extern crate variant_count;
 extern crate field_types;
@@ -43,6 +44,41 @@ struct TestTypeDerive {
     second: bool,
 }
 
+#[derive(FieldType)]
+#[field_type_derive(Debug, Clone, PartialEq)]
+struct TestSkipGen<'a, T: 'a + Clone, U>
+    where U: 'a + Default
+{
+    first: U,
+    #[field_type(skip)]
+    second: &'a T,
+}
+
+#[derive(FieldType)]
+#[field_type_derive(Debug, Clone, PartialEq)]
+struct TestSkipAllGen<'a, T> {
+    first: i32,
+    #[field_type(skip)]
+    second: &'a T,
+}
+
+#[derive(FieldType)]
+#[field_type_derive(Debug, Clone, PartialEq)]
+struct TestConstGen<T, const N: usize> {
+    first: [T; N],
+    second: usize,
+    #[field_type(skip)]
+    third: T,
+}
+
+#[derive(FieldType)]
+#[field_type_derive(Debug, Clone, PartialEq)]
+struct TestSkipConstGen<T, const N: usize> {
+    first: T,
+    #[field_type(skip)]
+    second: [T; N],
+}
+
 #[test]
 fn full_field_type_variants() {
     let _field = TestFieldType::First(2);
@@ -137,3 +173,77 @@ fn into_field_type() {
     assert_eq!(TestTypesDeriveFieldType::First(1), fields[0]);
     assert_eq!(TestTypesDeriveFieldType::Second(true), fields[1]);
 }
+
+#[test]
+fn skipped_generics_field_type() {
+    let value = "test".to_string();
+    let test = TestSkipGen { first: 1u8, second: &value };
+    let fields: [TestSkipGenFieldType<u8>; 1] = test.into();
+    assert_eq!([TestSkipGenFieldType::First(1)], fields);
+
+    let test = TestSkipAllGen { first: 1, second: &value };
+    let fields: [TestSkipAllGenFieldType; 1] = test.into_field_type_array();
+    assert_eq!([TestSkipAllGenFieldType::First(1)], fields);
+
+    let test = TestConstGen { first: [1, 2], second: 2, third: 0 };
+    let fields: [TestConstGenFieldType<i32, 2>; 2] = test.into();
+    assert_eq!([TestConstGenFieldType::First([1, 2]), TestConstGenFieldType::Second(2)], fields);
+
+    let test = TestSkipConstGen::<_, 2> { first: 1, second: [2, 3] };
+    let fields: [TestSkipConstGenFieldType<i32>; 1] = test.into_field_type_array();
+    assert_eq!([TestSkipConstGenFieldType::First(1)], fields);
+}
+
+fn convert<S, A: From<S>>(source: S) -> A {
+    A::from(source)
+}
+
+#[test]
+fn from_field_type() {
+    let message = "test".to_string();
+    let test = TestGen {
+        first: 1,
+        second_field: Some(&message),
+        third: &2,
+        fourth: message.clone(),
+    };
+    let fields: [TestGenFieldType<i32, String>; 2] = convert(test);
+    assert!(matches!(fields, [TestGenFieldType::First(1), TestGenFieldType::SecondField(Some(s))] if s == &message));
+
+    let test = TestConstGen { first: [1, 2, 3], second: 3, third: 0 };
+    let fields: [TestConstGenFieldType<i32, 3>; 2] = convert(test);
+    assert_eq!([TestConstGenFieldType::First([1, 2, 3]), TestConstGenFieldType::Second(3)], fields);
+}
+
+#[test]
+fn iter_field_type() {
+    let test = TestTypesDerive {
+        first: 1,
+        second: true,
+    };
+    let fields = test.into_field_types();
+    assert_eq!(2, fields.len());
+    assert_eq!(vec![TestTypesDeriveFieldType::Second(true), TestTypesDeriveFieldType::First(1)], fields.rev().collect::<Vec<_>>());
+
+    let test = TestTypesDerive {
+        first: 1,
+        second: true,
+    };
+    let mut fields = Vec::new();
+    for field in test {
+        fields.push(field);
+    }
+    assert_eq!(vec![TestTypesDeriveFieldType::First(1), TestTypesDeriveFieldType::Second(true)], fields);
+
+    let message = "test".to_string();
+    let test = TestGen {
+        first: 1,
+        second_field: Some(&message),
+        third: &2,
+        fourth: message.clone(),
+    };
+    let firsts = test.into_iter()
+        .filter(|field| matches!(field, TestGenFieldType::First(_)))
+        .count();
+    assert_eq!(1, firsts);
+}
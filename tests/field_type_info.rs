@@ -0,0 +1,57 @@
+#![allow(dead_code)]
+
+extern crate field_types;
+
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use field_types::FieldName;
+
+#[derive(FieldName)]
+struct Test {
+    first: i32,
+    second_field: Option<String>,
+    third: std::collections::HashMap<String, Vec<u8>>,
+    fourth: [u8; 4],
+    fifth: (i32, &'static str),
+    sixth: Box<dyn Fn(i32) -> u8 + Send>,
+    #[field_name(skip)]
+    seventh: bool,
+}
+
+#[derive(FieldName)]
+struct TestGen<'a, T: Debug> {
+    first: T,
+    second_field: &'a mut str,
+    third: Vec<T>,
+    fourth: HashMap<u32, String>,
+    fifth: Box<dyn Iterator<Item = u8> + 'a>,
+}
+
+#[test]
+fn type_name() {
+    assert_eq!("i32", TestFieldName::First.type_name());
+    assert_eq!("Option<String>", TestFieldName::SecondField.type_name());
+    assert_eq!("std::collections::HashMap<String, Vec<u8>>", TestFieldName::Third.type_name());
+    assert_eq!("[u8; 4]", TestFieldName::Fourth.type_name());
+    assert_eq!("(i32, &'static str)", TestFieldName::Fifth.type_name());
+    assert_eq!("Box<dyn Fn(i32) -> u8 + Send>", TestFieldName::Sixth.type_name());
+
+    assert_eq!("T", TestGenFieldName::First.type_name());
+    assert_eq!("&'a mut str", TestGenFieldName::SecondField.type_name());
+    assert_eq!("Vec<T>", TestGenFieldName::Third.type_name());
+    assert_eq!("Box<dyn Iterator<Item = u8> + 'a>", TestGenFieldName::Fifth.type_name());
+}
+
+#[test]
+fn type_id() {
+    assert_eq!(Some(TypeId::of::<i32>()), TestFieldName::First.type_id());
+    assert_eq!(Some(TypeId::of::<Option<String>>()), TestFieldName::SecondField.type_id());
+    assert_eq!(Some(TypeId::of::<(i32, &'static str)>()), TestFieldName::Fifth.type_id());
+
+    assert_eq!(None, TestGenFieldName::First.type_id());
+    assert_eq!(None, TestGenFieldName::SecondField.type_id());
+    assert_eq!(None, TestGenFieldName::Third.type_id());
+    assert_eq!(Some(TypeId::of::<HashMap<u32, String>>()), TestGenFieldName::Fourth.type_id());
+    assert_eq!(None, TestGenFieldName::Fifth.type_id());
+}
@@ -1,4 +1,5 @@
 #![allow(dead_code)]
+#![allow(clippy::match_like_matches_macro)]
 
 extern crate variant_count;
 extern crate field_types;
@@ -17,6 +18,15 @@ struct Test {
     fourth: bool,
 }
 
+#[derive(FieldType, FieldName)]
+#[field_name(name = "TestRenamedKey", array_method = "keys")]
+#[field_type(name = "TestRenamedValue", array_method = "into_values")]
+#[field_types_derive(Debug, Clone, PartialEq)]
+struct TestRenamed {
+    first: i32,
+    second_field: bool,
+}
+
 impl Test {
     fn foo() -> bool {
         true
@@ -55,7 +65,45 @@ mod bar {
     }
 }
 
+mod baz {
+    use field_types::{FieldType, FieldName};
+
+    #[derive(Debug, PartialEq)]
+    pub(crate) struct Secret(pub u8);
+
+    #[derive(FieldType, FieldName)]
+    #[field_types(visible_only)]
+    #[field_type_derive(Debug, PartialEq)]
+    pub struct TestVisible {
+        pub first: i32,
+        pub(crate) second: bool,
+        third: u8,
+    }
+
+    impl TestVisible {
+        pub fn new(first: i32, second: bool) -> Self {
+            TestVisible { first, second, third: 0 }
+        }
+    }
+
+    #[derive(FieldType, FieldName)]
+    #[field_types(vis = "pub(crate)", visible_only)]
+    #[field_type_derive(Debug, PartialEq)]
+    pub struct TestVis {
+        pub first: i32,
+        pub(crate) secret: Secret,
+        third: u8,
+    }
+
+    impl TestVis {
+        pub fn new(first: i32, secret: u8) -> Self {
+            TestVis { first, secret: Secret(secret), third: 0 }
+        }
+    }
+}
+
 use bar::{TestGen, TestGenFieldType, TestGenFieldName};
+use baz::{TestVisible, TestVisibleFieldType, TestVisibleFieldName, TestVis, TestVisFieldType, TestVisFieldName, Secret};
 
 #[test]
 fn full_field_types_variants() {
@@ -182,3 +230,29 @@ fn field_name_str() {
     assert_eq!(Some(TestFieldName::SecondField), TestFieldName::by_name("second_field"));
     assert_eq!(None, TestFieldName::by_name("third"));
 }
+
+#[test]
+fn field_types_visibility() {
+    let test = TestVisible::new(1, true);
+    assert_eq!([TestVisibleFieldType::First(1)], test.into_field_type_array());
+    assert_eq!([TestVisibleFieldName::First], TestVisible::as_field_name_array());
+    assert_eq!(None, TestVisibleFieldName::by_name("second"));
+
+    let test = TestVis::new(1, 2);
+    assert_eq!([TestVisFieldType::First(1), TestVisFieldType::Secret(Secret(2))], test.into_field_type_array());
+    assert_eq!([TestVisFieldName::First, TestVisFieldName::Secret], TestVis::as_field_name_array());
+    assert_eq!(None, TestVisFieldName::by_name("third"));
+}
+
+#[test]
+fn field_types_renamed() {
+    let test = TestRenamed { first: 1, second_field: true };
+    assert_eq!([TestRenamedValue::First(1), TestRenamedValue::SecondField(true)], test.into_values());
+
+    let test = TestRenamed { first: 1, second_field: true };
+    let values: [TestRenamedValue; 2] = test.into();
+    assert_eq!([TestRenamedValue::First(1), TestRenamedValue::SecondField(true)], values);
+
+    assert_eq!([TestRenamedKey::First, TestRenamedKey::SecondField], TestRenamed::keys());
+    assert_eq!("second_field", TestRenamedKey::SecondField.name());
+}
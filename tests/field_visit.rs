@@ -0,0 +1,100 @@
+#![allow(dead_code)]
+
+extern crate field_types;
+
+use std::fmt::{Debug, Display, Write};
+use field_types::FieldName;
+
+#[derive(FieldName)]
+#[field_name(visit(Debug))]
+struct Test {
+    first: i32,
+    second_field: Option<String>,
+    #[field_name(skip)]
+    third: Vec<u8>,
+}
+
+#[derive(FieldName)]
+#[field_types(visit(Debug, Display))]
+struct TestGen<'a, T> {
+    first: T,
+    second_field: &'a str,
+}
+
+pub trait Counter {
+    fn increment(&mut self);
+}
+
+impl Counter for u32 {
+    fn increment(&mut self) {
+        *self += 1;
+    }
+}
+
+impl Counter for u64 {
+    fn increment(&mut self) {
+        *self += 10;
+    }
+}
+
+#[derive(FieldName)]
+#[field_name(visit(Counter))]
+struct TestCounters {
+    first: u32,
+    second: u64,
+}
+
+#[test]
+fn for_each_field_debug() {
+    let test = Test {
+        first: 1,
+        second_field: Some("test".to_string()),
+        third: vec![],
+    };
+    let mut output = String::new();
+    test.for_each_field(|name, value| {
+        write!(output, "{}={:?};", name.name(), value).unwrap();
+    });
+    assert_eq!(r#"first=1;second_field=Some("test");"#, output);
+}
+
+#[test]
+fn for_each_field_debug_display() {
+    let test = TestGen {
+        first: 1.5,
+        second_field: "test",
+    };
+    let mut output = Vec::new();
+    test.for_each_field(|name, value| {
+        output.push(format!("{}: {} / {:?}", name.name(), value, value));
+    });
+    assert_eq!(vec!["first: 1.5 / 1.5", r#"second_field: test / "test""#], output);
+
+    let debug: Vec<_> = {
+        let mut debug = Vec::new();
+        test.for_each_field(|_, value| {
+            let value: &dyn Debug = value;
+            debug.push(format!("{:?}", value));
+        });
+        debug
+    };
+    assert_eq!(vec!["1.5", r#""test""#], debug);
+
+    fn display_all<T: Debug + Display>(test: &TestGen<'_, T>) -> String {
+        let mut output = String::new();
+        test.for_each_field(|_, value| output.push_str(&value.to_string()));
+        output
+    }
+    assert_eq!("1.5test", display_all(&test));
+}
+
+#[test]
+fn for_each_field_mut() {
+    let mut test = TestCounters {
+        first: 1,
+        second: 2,
+    };
+    test.for_each_field_mut(|_, value| value.increment());
+    assert_eq!(2, test.first);
+    assert_eq!(12, test.second);
+}
@@ -0,0 +1,94 @@
+#![cfg(feature = "serde_json")]
+#![allow(dead_code)]
+
+extern crate field_types;
+
+use field_types::{FieldName, MergePatchError};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+struct Address {
+    city: String,
+    zip: Option<String>,
+}
+
+#[derive(FieldName, Clone, Debug, PartialEq)]
+#[field_name(json_patch)]
+struct Test {
+    first: i32,
+    second_field: Option<String>,
+    address: Address,
+    #[field_name(skip)]
+    third: bool,
+}
+
+fn test() -> Test {
+    Test {
+        first: 1,
+        second_field: Some("test".to_string()),
+        address: Address {
+            city: "Paris".to_string(),
+            zip: Some("75001".to_string()),
+        },
+        third: true,
+    }
+}
+
+#[test]
+fn json_patch() {
+    let old = test();
+    let mut new = test();
+    assert_eq!(json!([]), old.json_patch(&new).unwrap());
+
+    new.second_field = None;
+    new.address.city = "Berlin".to_string();
+    new.third = false;
+    assert_eq!(
+        json!([
+            { "op": "replace", "path": "/second_field", "value": null },
+            { "op": "replace", "path": "/address", "value": { "city": "Berlin", "zip": "75001" } },
+        ]),
+        old.json_patch(&new).unwrap()
+    );
+}
+
+#[test]
+fn apply_merge_patch() {
+    let mut test = test();
+    test.apply_merge_patch(&json!({
+        "first": 2,
+        "second_field": null,
+        "address": { "zip": null },
+    })).unwrap();
+
+    let mut expected = self::test();
+    expected.first = 2;
+    expected.second_field = None;
+    expected.address.zip = None;
+    assert_eq!(expected, test);
+}
+
+#[test]
+fn apply_merge_patch_errors() {
+    let mut test = test();
+
+    assert!(matches!(test.apply_merge_patch(&json!([1])), Err(MergePatchError::NotAnObject)));
+
+    let error = test.apply_merge_patch(&json!({ "first": 2, "third": false })).unwrap_err();
+    assert!(matches!(error, MergePatchError::UnknownField(ref name) if name == "third"));
+    assert_eq!("unknown field `third` in merge patch", error.to_string());
+
+    let error = test.apply_merge_patch(&json!({ "first": 3, "address": { "city": 1 } })).unwrap_err();
+    assert!(matches!(error, MergePatchError::Field { field: TestFieldName::Address, .. }));
+    assert_eq!(
+        "invalid value of field `address`: invalid type: integer `1`, expected a string",
+        error.to_string()
+    );
+
+    let error = test.apply_merge_patch(&json!({ "first": null })).unwrap_err();
+    assert!(matches!(error, MergePatchError::Field { field: TestFieldName::First, .. }));
+
+    // The failed patches are not applied partially
+    assert_eq!(self::test(), test);
+}
@@ -0,0 +1,116 @@
+#![no_std]
+#![allow(dead_code)]
+
+extern crate std;
+
+use field_types::{FieldType, FieldName};
+
+#[derive(FieldType, FieldName)]
+#[field_type_derive(Debug, PartialEq)]
+struct Test {
+    first: i32,
+    second_field: Option<&'static str>,
+    #[field_types(skip)]
+    third: bool,
+}
+
+#[derive(FieldType, FieldName)]
+#[field_type_derive(Debug, PartialEq)]
+struct TestGen<'a, T: 'a> {
+    first: T,
+    second_field: Option<&'a T>,
+}
+
+#[no_implicit_prelude]
+mod no_prelude {
+    use ::field_types::{FieldType, FieldName};
+
+    #[derive(FieldType, FieldName)]
+    #[field_name(any, field_keys, partial)]
+    #[field_types(tracked)]
+    pub struct Test {
+        pub first: i32,
+        pub second_field: ::core::option::Option<&'static str>,
+    }
+
+    #[derive(FieldType, FieldName)]
+    pub struct TestFlatten {
+        #[field_types(flatten)]
+        pub test: Test,
+        #[field_types(flatten(prefix = "other_"))]
+        pub other: Test,
+        pub third: bool,
+    }
+
+    #[derive(FieldName)]
+    pub struct TestNested {
+        #[field_name(nested)]
+        pub test: Test,
+    }
+
+    #[derive(FieldType, FieldName)]
+    #[field_name(field_keys)]
+    pub struct TestGen<'a, T: 'a> {
+        pub first: T,
+        pub second_field: ::core::option::Option<&'a T>,
+    }
+}
+
+mod shadowed {
+    use field_types::{FieldType, FieldName};
+
+    struct Option;
+    struct Some;
+    struct None;
+    trait From {}
+    trait Into {}
+
+    #[derive(FieldType, FieldName)]
+    pub struct Test {
+        pub first: i32,
+        pub second_field: bool,
+    }
+}
+
+#[test]
+fn no_std_field_types() {
+    let test = Test {
+        first: 1,
+        second_field: Some("test"),
+        third: true,
+    };
+    let fields: [TestFieldType; 2] = test.into();
+    assert_eq!([TestFieldType::First(1), TestFieldType::SecondField(Some("test"))], fields);
+    assert_eq!([TestFieldName::First, TestFieldName::SecondField], Test::as_field_name_array());
+    assert_eq!(Some(TestFieldName::SecondField), TestFieldName::by_name("second_field"));
+
+    let value = 2;
+    let test = TestGen {
+        first: 1,
+        second_field: Some(&value),
+    };
+    assert_eq!([TestGenFieldType::First(1), TestGenFieldType::SecondField(Some(&2))], test.into_field_type_array());
+}
+
+#[test]
+fn no_prelude_field_types() {
+    let test = no_prelude::Test {
+        first: 1,
+        second_field: None,
+    };
+    assert!(matches!(test.into_field_type_array(), [no_prelude::TestFieldType::First(1), no_prelude::TestFieldType::SecondField(None)]));
+    assert_eq!(Some(no_prelude::TestFieldName::First), no_prelude::TestFieldName::by_name("first"));
+
+    let test = no_prelude::TestGen {
+        first: 1,
+        second_field: None,
+    };
+    assert!(matches!(test.into_field_type_array(), [no_prelude::TestGenFieldType::First(1), no_prelude::TestGenFieldType::SecondField(None)]));
+
+    let test = shadowed::Test {
+        first: 1,
+        second_field: true,
+    };
+    assert!(matches!(test.into_field_type_array(), [shadowed::TestFieldType::First(1), shadowed::TestFieldType::SecondField(true)]));
+    assert_eq!(Some(shadowed::TestFieldName::SecondField), shadowed::TestFieldName::by_name("second_field"));
+}
@@ -0,0 +1,64 @@
+#![cfg(feature = "serde")]
+#![allow(dead_code)]
+
+extern crate field_types;
+
+use field_types::FieldName;
+
+#[derive(FieldName)]
+#[field_name(serde)]
+struct Test {
+    first: i32,
+    second_field: Option<String>,
+    #[field_name(skip)]
+    third: bool,
+}
+
+#[derive(FieldName)]
+#[field_name(name = "MessageKey", serde)]
+struct Message {
+    kind: u32,
+    #[field_types(flatten)]
+    test: Test,
+}
+
+// Without `serde` option, the serde traits can be derived for the enum as usual
+#[derive(FieldName)]
+#[field_name_derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+struct TestDerived {
+    first: i32,
+    second_field: bool,
+}
+
+#[test]
+fn serialize_field_name() {
+    assert_eq!(r#""second_field""#, serde_json::to_string(&TestFieldName::SecondField).unwrap());
+    assert_eq!(
+        r#"["kind","first"]"#,
+        serde_json::to_string(&[MessageKey::Kind, MessageKey::Test(TestFieldName::First)]).unwrap()
+    );
+}
+
+#[test]
+fn deserialize_field_name() {
+    assert_eq!(TestFieldName::SecondField, serde_json::from_str::<TestFieldName>(r#""second_field""#).unwrap());
+    assert_eq!(MessageKey::Test(TestFieldName::First), serde_json::from_str::<MessageKey>(r#""first""#).unwrap());
+
+    let error = serde_json::from_str::<TestFieldName>(r#""third""#).unwrap_err();
+    assert_eq!(
+        "unknown field name `third`, expected one of: `first`, `second_field` at line 1 column 7",
+        error.to_string()
+    );
+
+    let error = serde_json::from_str::<TestFieldName>("1").unwrap_err();
+    assert_eq!(
+        "invalid type: integer `1`, expected one of the field names: `first`, `second_field` at line 1 column 1",
+        error.to_string()
+    );
+}
+
+#[test]
+fn derived_field_name() {
+    assert_eq!(r#""SecondField""#, serde_json::to_string(&TestDerivedFieldName::SecondField).unwrap());
+    assert_eq!(TestDerivedFieldName::First, serde_json::from_str::<TestDerivedFieldName>(r#""First""#).unwrap());
+}
@@ -0,0 +1,116 @@
+#![cfg(all(feature = "serde", feature = "alloc"))]
+#![allow(dead_code)]
+
+extern crate field_types;
+
+use field_types::FieldType;
+use serde::{Deserialize, Serialize};
+
+#[derive(FieldType)]
+#[field_type(serde)]
+#[field_type_derive(Debug, PartialEq)]
+struct Test {
+    first: i32,
+    second_field: Option<String>,
+    #[field_type(skip)]
+    third: bool,
+}
+
+#[derive(FieldType)]
+#[field_type(serde)]
+#[field_type_derive(Debug, PartialEq)]
+struct Message<'a, T> {
+    #[field_types(flatten)]
+    test: Test,
+    kind: &'a str,
+    body: T,
+}
+
+#[derive(FieldType)]
+#[field_type(serde)]
+#[field_type_derive(Debug, PartialEq)]
+struct Pair {
+    #[field_types(flatten(prefix = "left_"))]
+    left: Test,
+    #[field_types(flatten(prefix = "right_"))]
+    right: Test,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Update {
+    id: u32,
+    #[serde(with = "field_types::serde_sparse")]
+    changes: Vec<TestFieldType>,
+}
+
+#[test]
+fn serialize_field_type() {
+    assert_eq!(
+        r#"{"second_field":"x"}"#,
+        serde_json::to_string(&TestFieldType::SecondField(Some("x".to_string()))).unwrap()
+    );
+    assert_eq!(
+        r#"[{"first":1},{"kind":"test"},{"body":[1,2]}]"#,
+        serde_json::to_string(&[
+            MessageFieldType::Test(TestFieldType::First(1)),
+            MessageFieldType::Kind("test"),
+            MessageFieldType::Body(vec![1, 2]),
+        ]).unwrap()
+    );
+}
+
+#[test]
+fn deserialize_field_type() {
+    assert_eq!(
+        TestFieldType::SecondField(Some("x".to_string())),
+        serde_json::from_str::<TestFieldType>(r#"{"second_field":"x"}"#).unwrap()
+    );
+    assert_eq!(
+        MessageFieldType::Test(TestFieldType::SecondField(None)),
+        serde_json::from_str::<MessageFieldType<u8>>(r#"{"second_field":null}"#).unwrap()
+    );
+    assert_eq!(
+        MessageFieldType::Kind("test"),
+        serde_json::from_str::<MessageFieldType<u8>>(r#"{"kind":"test"}"#).unwrap()
+    );
+
+    let error = serde_json::from_str::<MessageFieldType<u8>>(r#"{"third":true}"#).unwrap_err();
+    assert_eq!(
+        "unknown field name `third`, expected one of: `first`, `second_field`, `kind`, `body` at line 1 column 8",
+        error.to_string()
+    );
+    assert!(serde_json::from_str::<TestFieldType>(r#"{}"#).is_err());
+    assert!(serde_json::from_str::<TestFieldType>(r#"{"first":1,"second_field":null}"#).is_err());
+    assert!(serde_json::from_str::<TestFieldType>(r#"{"first":"1"}"#).is_err());
+}
+
+#[test]
+fn sparse_field_types() {
+    let update = Update {
+        id: 1,
+        changes: vec![TestFieldType::SecondField(Some("x".to_string())), TestFieldType::First(2)],
+    };
+    let json = serde_json::to_string(&update).unwrap();
+    assert_eq!(r#"{"id":1,"changes":{"second_field":"x","first":2}}"#, json);
+    assert_eq!(update, serde_json::from_str(&json).unwrap());
+
+    let update: Update = serde_json::from_str(r#"{"id":2,"changes":{}}"#).unwrap();
+    assert!(update.changes.is_empty());
+
+    let error = serde_json::from_str::<Update>(r#"{"id":3,"changes":{"first":1,"first":2}}"#).unwrap_err();
+    assert_eq!("duplicate field `first` at line 1 column 36", error.to_string());
+}
+
+#[test]
+fn prefixed_field_type() {
+    let value = PairFieldType::Right(TestFieldType::First(1));
+    let json = serde_json::to_string(&value).unwrap();
+    assert_eq!(r#"{"right_first":1}"#, json);
+    assert_eq!(value, serde_json::from_str(&json).unwrap());
+
+    let error = serde_json::from_str::<PairFieldType>(r#"{"first":1}"#).unwrap_err();
+    assert_eq!(
+        "unknown field name `first`, expected one of: `left_first`, `left_second_field`, `right_first`, `right_second_field` at line 1 column 8",
+        error.to_string()
+    );
+}
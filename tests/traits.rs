@@ -0,0 +1,87 @@
+#![allow(dead_code)]
+
+extern crate field_types;
+
+use std::fmt::Debug;
+use field_types::{FieldType, FieldName, FieldTypes, FieldNames};
+
+#[derive(FieldType, FieldName)]
+#[field_type_derive(Debug, Clone, PartialEq)]
+struct Test {
+    first: i32,
+    second_field: Option<String>,
+    #[field_types(skip)]
+    third: bool,
+}
+
+#[derive(FieldType, FieldName)]
+#[field_types_derive(Debug, Clone, PartialEq)]
+struct TestGen<'a, T: 'a, U>
+    where U: 'a
+{
+    first: T,
+    second_field: Option<&'a U>,
+    #[field_type(skip)]
+    third: &'a T,
+    #[field_name = "skip"]
+    fourth: U,
+}
+
+fn names<T: FieldNames>() -> Vec<&'static str> {
+    T::field_names().map(|name| name.name()).collect()
+}
+
+fn dump<T: FieldTypes>(source: T) -> Vec<String>
+    where T::Value: Debug
+{
+    source.into_field_types().map(|value| format!("{:?}", value)).collect()
+}
+
+#[test]
+fn field_names_trait() {
+    assert_eq!(vec!["first", "second_field"], names::<Test>());
+    assert_eq!(vec!["first", "second_field", "third"], names::<TestGen<i32, bool>>());
+    assert_eq!(2, <Test as FieldNames>::COUNT);
+    assert_eq!(3, <TestGen<i32, bool> as FieldNames>::COUNT);
+    assert_eq!(2, <TestFieldName as field_types::FieldName>::COUNT);
+
+    let names = Test::field_names();
+    assert_eq!(2, names.len());
+    assert_eq!(vec![TestFieldName::SecondField, TestFieldName::First], names.rev().collect::<Vec<_>>());
+
+    assert_eq!(1, field_types::FieldName::index(&TestFieldName::SecondField));
+    assert_eq!(Some(TestFieldName::First), field_types::FieldName::from_index(0));
+    assert_eq!(None::<TestFieldName>, field_types::FieldName::from_index(2));
+    assert_eq!(Some(TestFieldName::First), <TestFieldName as field_types::FieldName>::by_name("first"));
+}
+
+#[test]
+fn field_types_trait() {
+    let test = Test {
+        first: 1,
+        second_field: Some("test".to_string()),
+        third: true,
+    };
+    assert_eq!(vec!["First(1)", "SecondField(Some(\"test\"))"], dump(test));
+    assert_eq!(2, <Test as FieldTypes>::COUNT);
+
+    let message = "test".to_string();
+    let test = TestGen {
+        first: 1,
+        second_field: Some(&message),
+        third: &2,
+        fourth: message.clone(),
+    };
+    assert_eq!(vec!["First(1)", "SecondField(Some(\"test\"))", "Fourth(\"test\")"], dump(test));
+
+    let test = TestGen {
+        first: 1,
+        second_field: None,
+        third: &2,
+        fourth: 3,
+    };
+    let mut values = test.into_field_types();
+    assert_eq!(3, values.len());
+    assert_eq!(Some(TestGenFieldType::Fourth(3)), values.next_back());
+    assert_eq!(Some(TestGenFieldType::First(1)), values.next());
+}